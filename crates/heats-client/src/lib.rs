@@ -1,75 +1,194 @@
-use std::io::{self, BufRead};
+use std::io;
+use std::time::Duration;
 
-use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::unix::OwnedReadHalf;
 use tokio::net::UnixStream;
 
+use heats_core::ipc::IpcContext;
+pub use heats_core::ipc::{IpcCommand, IpcReply, ResponseFormat, ACTION_ALT, ACTION_DEFAULT};
+
 /// IPC format for communication with daemon
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
 pub enum IpcFormat {
+    #[default]
     Text,
     Jsonl,
 }
 
-/// Read items from stdin, send them to the daemon, and return the selected item.
-/// Returns `Ok(Some(selected))` if user selected, `Ok(None)` if cancelled,
+/// Session options sent to the daemon in the IPC context line
+#[derive(Debug, Clone, Default)]
+pub struct ClientOptions {
+    pub format: IpcFormat,
+    pub response: ResponseFormat,
+    /// Return as soon as a non-empty query leaves a single match
+    pub auto_select: bool,
+    /// Initial query
+    pub query: Option<String>,
+    /// Cancel after this many milliseconds without a selection
+    pub timeout_ms: Option<u64>,
+    /// Placeholder text for the search input
+    pub prompt: Option<String>,
+    /// Glyph at the left of the search input
+    pub icon: Option<String>,
+    /// Allow selecting several items (one response line each)
+    pub multi: bool,
+    /// Mask the typed query
+    pub password: bool,
+    /// Stream stdin to an already-open session (see `send_live_and_receive`)
+    pub live: bool,
+    /// Report the selection as a JSON object of these item fields
+    pub fields: Vec<String>,
+}
+
+/// What the user chose in a session
+#[derive(Debug, Clone)]
+pub struct Selection {
+    /// How it was chosen: [`ACTION_DEFAULT`] (Enter) or [`ACTION_ALT`] (Alt+Enter)
+    pub action: String,
+    /// The selected item(s) in the requested response format, one per line
+    pub value: String,
+}
+
+/// Forward stdin to the daemon line by line as it is read, then return the selection.
+/// Returns `Ok(Some(selection))` if the user selected, `Ok(None)` if cancelled,
 /// and `Err` if the daemon is unreachable or an I/O error occurs.
-pub async fn send_and_receive(
-    items: Vec<String>,
-    format: IpcFormat,
-) -> io::Result<Option<String>> {
+/// With `multi`, `selection.value` holds one line per chosen item.
+///
+/// Nothing is collected first, so huge or slow pipes never sit in memory, and socket
+/// writes apply backpressure to the reader. The session still opens only once stdin
+/// reaches EOF; `send_live_and_receive` (`--live`) shows items while they arrive.
+/// An empty stdin is an `InvalidInput` error.
+pub async fn send_stdin_and_receive(options: &ClientOptions) -> io::Result<Option<Selection>> {
+    let (reader, mut writer) = connect().await?.into_split();
+    writer.write_all(context_line(options)?.as_bytes()).await?;
+
+    let mut lines = BufReader::new(tokio::io::stdin()).lines();
+    let mut sent = 0usize;
+    loop {
+        let line = match lines.next_line().await {
+            Ok(Some(line)) => line,
+            Ok(None) => break,
+            // Skip lines that aren't valid UTF-8
+            Err(e) if e.kind() == io::ErrorKind::InvalidData => continue,
+            Err(e) => return Err(e),
+        };
+        if line.is_empty() {
+            continue;
+        }
+        writer.write_all(line.as_bytes()).await?;
+        writer.write_all(b"\n").await?;
+        sent += 1;
+    }
+    // Signal end of items
+    writer.shutdown().await?;
+
+    if sent == 0 {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "no items received from stdin",
+        ));
+    }
+    read_response(reader).await
+}
+
+/// Like `send_stdin_and_receive`, but the session opens immediately and stdin lines are
+/// forwarded as they arrive, so the list fills while the producer is still running.
+/// The selection can be made (and is returned) before stdin reaches EOF.
+pub async fn send_live_and_receive(options: &ClientOptions) -> io::Result<Option<Selection>> {
+    let options = ClientOptions {
+        live: true,
+        ..options.clone()
+    };
+    let (reader, mut writer) = connect().await?.into_split();
+    writer.write_all(context_line(&options)?.as_bytes()).await?;
+
+    // Forward stdin in the background; write errors just mean the session has ended
+    tokio::spawn(async move {
+        let mut lines = BufReader::new(tokio::io::stdin()).lines();
+        while let Ok(Some(line)) = lines.next_line().await {
+            if line.is_empty() {
+                continue;
+            }
+            let line = format!("{line}\n");
+            if writer.write_all(line.as_bytes()).await.is_err() {
+                return;
+            }
+        }
+        // Signal end of items
+        let _ = writer.shutdown().await;
+    });
+
+    read_response(reader).await
+}
+
+/// Send a control request and read the daemon's reply.
+pub async fn send_command(command: IpcCommand) -> io::Result<IpcReply> {
+    let mut stream = connect().await?;
+    let command = serde_json::to_string(&command).map_err(io::Error::other)?;
+    stream.write_all(format!("{command}\n").as_bytes()).await?;
+    stream.shutdown().await?;
+
+    let mut reply = String::new();
+    stream.read_to_string(&mut reply).await?;
+    serde_json::from_str(reply.trim()).map_err(io::Error::other)
+}
+
+/// Check that heatsd is running and answering IPC within a second.
+pub async fn ping() -> bool {
+    let reply = tokio::time::timeout(Duration::from_secs(1), send_command(IpcCommand::Ping));
+    matches!(reply.await, Ok(Ok(IpcReply { ok: true })))
+}
+
+async fn connect() -> io::Result<UnixStream> {
     let sock_path = heats_core::ipc::socket_path();
 
-    let stream = UnixStream::connect(&sock_path).await.map_err(|e| {
+    UnixStream::connect(&sock_path).await.map_err(|e| {
         io::Error::new(
             e.kind(),
             format!("heatsd is not running ({})", sock_path.display()),
         )
-    })?;
-
-    let (reader, mut writer) = stream.into_split();
+    })
+}
 
-    // Send context line
-    let context = match format {
-        IpcFormat::Text => r#"{"format":"text"}"#,
-        IpcFormat::Jsonl => r#"{"format":"jsonl"}"#,
+/// The JSON context line (newline-terminated) describing the session.
+fn context_line(options: &ClientOptions) -> io::Result<String> {
+    let context = IpcContext {
+        format: match options.format {
+            IpcFormat::Text => "text",
+            IpcFormat::Jsonl => "jsonl",
+        }
+        .to_string(),
+        response: options.response,
+        auto_select: options.auto_select,
+        query: options.query.clone(),
+        timeout_ms: options.timeout_ms,
+        prompt: options.prompt.clone(),
+        icon: options.icon.clone(),
+        multi: options.multi,
+        password: options.password,
+        live: options.live,
+        fields: options.fields.clone(),
+        report_action: true,
     };
-    writer.write_all(context.as_bytes()).await?;
-    writer.write_all(b"\n").await?;
-
-    // Send items as newline-delimited text
-    for item in &items {
-        writer.write_all(item.as_bytes()).await?;
-        writer.write_all(b"\n").await?;
-    }
-    // Signal end of items
-    writer.shutdown().await?;
+    let context = serde_json::to_string(&context).map_err(io::Error::other)?;
+    Ok(format!("{context}\n"))
+}
 
-    // Read response (selected item or empty = cancelled)
+/// Read the response: the selected item(s), or nothing if cancelled.
+async fn read_response(reader: OwnedReadHalf) -> io::Result<Option<Selection>> {
     let mut buf_reader = BufReader::new(reader);
     let mut response = String::new();
-    buf_reader.read_line(&mut response).await?;
+    buf_reader.read_to_string(&mut response).await?;
 
+    // The action line comes first (`report_action`), then the selection
     let trimmed = response.trim();
     if trimmed.is_empty() {
-        Ok(None)
-    } else {
-        Ok(Some(trimmed.to_string()))
+        return Ok(None);
     }
-}
-
-/// Read all lines from stdin (blocking).
-pub fn read_stdin_items() -> Vec<String> {
-    let stdin = io::stdin();
-    stdin
-        .lock()
-        .lines()
-        .filter_map(|line| {
-            let line = line.ok()?;
-            if line.is_empty() {
-                None
-            } else {
-                Some(line)
-            }
-        })
-        .collect()
+    let (action, value) = trimmed.split_once('\n').unwrap_or((trimmed, ""));
+    Ok(Some(Selection {
+        action: action.to_string(),
+        value: value.to_string(),
+    }))
 }
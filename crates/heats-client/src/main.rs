@@ -1,45 +1,134 @@
 use std::process;
 
-use heats_client::{read_stdin_items, send_and_receive, IpcFormat};
+use heats_client::{
+    ping, send_command, send_live_and_receive, send_stdin_and_receive, ClientOptions, IpcCommand,
+    IpcFormat, ResponseFormat, ACTION_ALT,
+};
+
+/// Exit status when the selection was made with Alt+Enter (Enter exits 0)
+const EXIT_ALT_ACTION: i32 = 10;
 
 fn main() {
     let args: Vec<String> = std::env::args().collect();
 
-    // Parse --format flag
-    let format = if args.iter().any(|a| a == "--format") {
-        let idx = args.iter().position(|a| a == "--format").unwrap();
-        match args.get(idx + 1).map(|s| s.as_str()) {
-            Some("jsonl") => IpcFormat::Jsonl,
-            Some("text") => IpcFormat::Text,
-            Some(other) => {
-                eprintln!("heats: unknown format '{other}', expected 'text' or 'jsonl'");
-                process::exit(2);
-            }
-            None => {
-                eprintln!("heats: --format requires a value");
+    let rt = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .expect("Failed to create tokio runtime");
+
+    // --ping: exit 0 if heatsd is up and responsive, 1 otherwise
+    if has_flag(&args, "--ping") {
+        let alive = rt.block_on(ping());
+        process::exit(if alive { 0 } else { 1 });
+    }
+
+    // --show <mode> / --hide: open or close the launcher without reading stdin
+    let command = match flag_value(&args, "--show") {
+        Some(mode) => Some(IpcCommand::Show { mode }),
+        None => has_flag(&args, "--hide").then_some(IpcCommand::Hide),
+    };
+    if let Some(command) = command {
+        match rt.block_on(send_command(command)) {
+            Ok(reply) if reply.ok => process::exit(0),
+            Ok(_) => process::exit(1),
+            Err(e) => {
+                eprintln!("heats: {e}");
                 process::exit(2);
             }
         }
-    } else {
-        IpcFormat::Text
+    }
+
+    // Parse --format flag
+    let format = match flag_value(&args, "--format").as_deref() {
+        Some("jsonl") => IpcFormat::Jsonl,
+        Some("text") | None => IpcFormat::Text,
+        Some(other) => {
+            eprintln!("heats: unknown format '{other}', expected 'text' or 'jsonl'");
+            process::exit(2);
+        }
     };
 
-    let items = read_stdin_items();
+    // --with-index: print "<index>\t<line>" so duplicate lines can be told apart
+    // --index: print only the 0-based index of the selected line
+    // --json: print {"index": N, "value": "...", "action": "..."}
+    let response_flags = [
+        ("--with-index", ResponseFormat::Indexed),
+        ("--index", ResponseFormat::Index),
+        ("--json", ResponseFormat::Json),
+    ];
+    let mut selected_flags = response_flags
+        .iter()
+        .filter(|(flag, _)| has_flag(&args, flag));
+    let response = match (selected_flags.next(), selected_flags.next()) {
+        (None, _) => ResponseFormat::Line,
+        (Some((_, response)), None) => *response,
+        (Some(_), Some(_)) => {
+            eprintln!("heats: --with-index, --index and --json are mutually exclusive");
+            process::exit(2);
+        }
+    };
 
-    if items.is_empty() {
-        eprintln!("heats: no items received from stdin");
+    // --fields title,data.pid: print {"title": "...", "data.pid": "..."} for the selection
+    let fields: Vec<String> = flag_value(&args, "--fields")
+        .map(|value| {
+            value
+                .split(',')
+                .map(str::trim)
+                .filter(|field| !field.is_empty())
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default();
+    if !fields.is_empty() && response != ResponseFormat::Line {
+        eprintln!("heats: --fields can't be combined with --with-index, --index or --json");
         process::exit(2);
     }
 
-    let rt = tokio::runtime::Builder::new_current_thread()
-        .enable_all()
-        .build()
-        .expect("Failed to create tokio runtime");
+    // --timeout <ms>: cancel (exit 1) if nothing is selected in time
+    let timeout_ms = flag_value(&args, "--timeout").map(|value| {
+        value.parse::<u64>().unwrap_or_else(|_| {
+            eprintln!("heats: --timeout expects milliseconds");
+            process::exit(2);
+        })
+    });
 
-    match rt.block_on(send_and_receive(items, format)) {
-        Ok(Some(selected)) => {
-            println!("{selected}");
-            process::exit(0);
+    let options = ClientOptions {
+        format,
+        response,
+        // --auto-select: return as soon as the typed query leaves a single match
+        auto_select: has_flag(&args, "--auto-select"),
+        // --query <text>: open with the filter already applied
+        query: flag_value(&args, "--query"),
+        timeout_ms,
+        // --prompt <text>: placeholder of the search input
+        prompt: flag_value(&args, "--prompt"),
+        // --icon <glyph>: shown at the left of the search input (e.g. an emoji)
+        icon: flag_value(&args, "--icon"),
+        // --multi: mark items with Tab, print one line per selected item
+        multi: has_flag(&args, "--multi"),
+        // --password: mask the typed query
+        password: has_flag(&args, "--password"),
+        // --live: open right away and append stdin lines as they arrive
+        live: has_flag(&args, "--live"),
+        fields,
+    };
+
+    let result = if options.live {
+        rt.block_on(send_live_and_receive(&options))
+    } else {
+        rt.block_on(send_stdin_and_receive(&options))
+    };
+
+    match result {
+        Ok(Some(selection)) => {
+            println!("{}", selection.value);
+            // 0 = Enter, 10 = Alt+Enter, so wrappers can branch on the action
+            let code = if selection.action == ACTION_ALT {
+                EXIT_ALT_ACTION
+            } else {
+                0
+            };
+            process::exit(code);
         }
         Ok(None) => {
             // Cancelled (Escape)
@@ -51,3 +140,19 @@ fn main() {
         }
     }
 }
+
+fn has_flag(args: &[String], flag: &str) -> bool {
+    args.iter().any(|a| a == flag)
+}
+
+/// Value following `flag`, or None if the flag is absent. Exits if the value is missing.
+fn flag_value(args: &[String], flag: &str) -> Option<String> {
+    let idx = args.iter().position(|a| a == flag)?;
+    match args.get(idx + 1) {
+        Some(value) => Some(value.clone()),
+        None => {
+            eprintln!("heats: {flag} requires a value");
+            process::exit(2);
+        }
+    }
+}
@@ -0,0 +1,147 @@
+use std::io::Write;
+use std::os::unix::fs::OpenOptionsExt;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::config::Config;
+use crate::source::DmenuItem;
+
+/// A provider's cached items as stored on disk
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct CacheEntry {
+    /// When the items were loaded (seconds since the Unix epoch)
+    pub updated_at: u64,
+    pub items: Vec<DmenuItem>,
+}
+
+impl CacheEntry {
+    /// Time elapsed since the items were loaded.
+    pub fn age(&self) -> Duration {
+        Duration::from_secs(unix_now().saturating_sub(self.updated_at))
+    }
+}
+
+pub(crate) fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Resolve the directory holding on-disk provider caches.
+pub fn cache_dir() -> PathBuf {
+    // Use ~/.cache/ (XDG convention) to match the config location
+    dirs::home_dir()
+        .expect("Could not determine home directory")
+        .join(".cache")
+        .join("heats")
+        .join("providers")
+}
+
+/// Resolve the cache file path for a provider.
+pub fn cache_file(provider: &str) -> PathBuf {
+    cache_dir().join(format!("{provider}.json"))
+}
+
+/// Read a provider's cache file. Returns None if it is missing or unreadable.
+pub fn read(provider: &str) -> Option<CacheEntry> {
+    let contents = std::fs::read_to_string(cache_file(provider)).ok()?;
+    match serde_json::from_str(&contents) {
+        Ok(entry) => Some(entry),
+        Err(e) => {
+            tracing::warn!("Ignoring corrupt cache for provider '{}': {}", provider, e);
+            None
+        }
+    }
+}
+
+/// Write a provider's items to its cache file, stamped with the current time.
+pub fn write(provider: &str, items: Vec<DmenuItem>) {
+    let entry = CacheEntry {
+        updated_at: unix_now(),
+        items,
+    };
+    write_json(&cache_file(provider), &entry);
+}
+
+/// Read a JSON list file (recent apps, histories). Empty if it is missing; a corrupt
+/// file is ignored with a warning.
+pub(crate) fn read_json_list<T: serde::de::DeserializeOwned>(path: &Path) -> Vec<T> {
+    let Ok(contents) = std::fs::read_to_string(path) else {
+        return Vec::new();
+    };
+    match serde_json::from_str(&contents) {
+        Ok(list) => list,
+        Err(e) => {
+            tracing::warn!("Ignoring corrupt {}: {}", path.display(), e);
+            Vec::new()
+        }
+    }
+}
+
+/// Save `value` as JSON to `path`, creating its directory. Writes to a temp file and
+/// renames it so a crash never leaves a truncated file. Files are owner-only, as some
+/// (the clipboard history) may sit in a shared /tmp.
+pub(crate) fn write_json(path: &Path, value: &impl serde::Serialize) {
+    let json = match serde_json::to_string(value) {
+        Ok(json) => json,
+        Err(e) => {
+            tracing::warn!("Failed to serialize {}: {}", path.display(), e);
+            return;
+        }
+    };
+
+    let tmp = path.with_extension("json.tmp");
+    let result = path
+        .parent()
+        .map_or(Ok(()), std::fs::create_dir_all)
+        .and_then(|()| {
+            std::fs::OpenOptions::new()
+                .write(true)
+                .create(true)
+                .truncate(true)
+                .mode(0o600)
+                .open(&tmp)
+        })
+        .and_then(|mut file| file.write_all(json.as_bytes()))
+        .and_then(|()| std::fs::rename(&tmp, path));
+    if let Err(e) = result {
+        tracing::warn!("Failed to write {}: {}", path.display(), e);
+    }
+}
+
+/// List cache files whose provider no longer exists in the config.
+pub fn orphaned_entries(config: &Config) -> Vec<PathBuf> {
+    let entries = match std::fs::read_dir(cache_dir()) {
+        Ok(entries) => entries,
+        Err(_) => return Vec::new(),
+    };
+
+    let mut orphans: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| match provider_name(path) {
+            Some(name) => !config.provider.contains_key(name),
+            None => false,
+        })
+        .collect();
+    orphans.sort();
+    orphans
+}
+
+/// Remove cache files for providers that are no longer configured.
+pub fn prune_orphans(config: &Config) {
+    for path in orphaned_entries(config) {
+        match std::fs::remove_file(&path) {
+            Ok(()) => tracing::info!("Removed orphaned cache file: {}", path.display()),
+            Err(e) => tracing::warn!("Failed to remove {}: {}", path.display(), e),
+        }
+    }
+}
+
+/// Extract the provider name from a `<provider>.json` cache file path.
+fn provider_name(path: &Path) -> Option<&str> {
+    if path.extension()? != "json" {
+        return None;
+    }
+    path.file_stem()?.to_str()
+}
@@ -0,0 +1,39 @@
+use std::path::PathBuf;
+
+use crate::cache::{read_json_list, unix_now, write_json};
+
+/// A piece of text copied while heatsd was watching the pasteboard
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct ClipboardEntry {
+    pub text: String,
+    /// When it was copied (seconds since the Unix epoch)
+    pub copied_at: u64,
+}
+
+/// Clipboard history file, in the runtime directory rather than `~/.cache`.
+/// On macOS that is usually `/tmp/xdg-runtime-<uid>/` ($XDG_RUNTIME_DIR is rarely set),
+/// so the history lasts until reboot, not just the login session.
+pub fn history_file() -> PathBuf {
+    crate::ipc::runtime_dir().join("clipboard.json")
+}
+
+/// Read the clipboard history, most recent first. Empty if missing or unreadable.
+pub fn read() -> Vec<ClipboardEntry> {
+    read_json_list(&history_file())
+}
+
+/// Put `text` at the front of the history (dropping an earlier copy of the same text),
+/// keep at most `max_entries`, and save it.
+pub fn record(text: &str, max_entries: usize) {
+    let mut entries = read();
+    entries.retain(|entry| entry.text != text);
+    entries.insert(
+        0,
+        ClipboardEntry {
+            text: text.to_string(),
+            copied_at: unix_now(),
+        },
+    );
+    entries.truncate(max_entries);
+    write_json(&history_file(), &entries);
+}
@@ -1,14 +1,25 @@
-use serde::Deserialize;
+use serde::{Deserialize, Deserializer};
 use std::collections::HashMap;
 use std::path::PathBuf;
 
+use crate::source::DmenuItem;
+
 #[derive(Debug, Deserialize)]
 #[serde(default)]
 pub struct Config {
     pub window: WindowConfig,
+    pub matcher: MatcherConfig,
+    pub log: LogConfig,
+    pub env: EnvConfig,
+    pub dmenu: DmenuConfig,
+    pub clipboard: ClipboardConfig,
     pub mode: Vec<ModeConfig>,
     pub provider: HashMap<String, ProviderConfig>,
     pub evaluator: HashMap<String, EvaluatorConfig>,
+    /// Command spawned every time the launcher is shown (fire-and-forget)
+    pub on_show: Option<Vec<String>>,
+    /// Command spawned every time the launcher is hidden (fire-and-forget)
+    pub on_hide: Option<Vec<String>>,
 }
 
 /// A mode: hotkey → providers mapping
@@ -19,6 +30,13 @@ pub struct ModeConfig {
     pub providers: Vec<String>,
     #[serde(default)]
     pub evaluators: Vec<String>,
+    /// Items always listed first when they match the query, by title or by the
+    /// provider's `field` value (e.g. an app path)
+    #[serde(default)]
+    pub pinned: Vec<String>,
+    /// Glyph (e.g. an emoji) shown at the left of the search input in this mode.
+    /// Only the first character is used.
+    pub icon: Option<String>,
 }
 
 /// How to pass input to a source/action command
@@ -35,6 +53,29 @@ impl Default for InputMode {
     }
 }
 
+/// Where an action's field value is inserted among its arguments
+#[derive(Debug, Clone, Copy, Default, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ActionPlacement {
+    /// After the configured arguments: `action... value`
+    #[default]
+    Append,
+    /// Right after the program, before the configured arguments: `program value args...`
+    Prepend,
+}
+
+impl ActionPlacement {
+    /// The action's arguments (without the program) with the field `values` inserted.
+    pub fn arguments<'a>(self, args: &'a [String], values: &'a [String]) -> Vec<&'a str> {
+        let args = args.iter().map(String::as_str);
+        let values = values.iter().map(String::as_str);
+        match self {
+            Self::Append => args.chain(values).collect(),
+            Self::Prepend => values.chain(args).collect(),
+        }
+    }
+}
+
 /// An evaluator: query-driven source + action
 #[derive(Debug, Clone, Deserialize)]
 pub struct EvaluatorConfig {
@@ -48,29 +89,160 @@ pub struct EvaluatorConfig {
     /// How to pass the field value to the action command
     #[serde(default)]
     pub action_input: InputMode,
+    /// Where the field value goes among the action's arguments (`action_input = "arg"`)
+    #[serde(default)]
+    pub action_placement: ActionPlacement,
     /// DmenuItem field to pass to the action
     #[serde(default = "default_field")]
     pub field: String,
+    /// Wait this long after the last keystroke before running. Default: 100
+    #[serde(default = "default_debounce_ms")]
+    pub debounce_ms: u64,
+    /// Minimum query length (in characters) before the evaluator runs. Default: 1
+    #[serde(default = "default_min_chars")]
+    pub min_chars: usize,
+    /// Only run when the query starts with this prefix (e.g. "="); the prefix is stripped
+    pub prefix: Option<String>,
+    /// Run `source` through `sh -c` (see `ProviderConfig::shell`)
+    #[serde(default)]
+    pub shell: bool,
+    /// Post a macOS notification after the action runs (see `ProviderConfig::notify`)
+    #[serde(default)]
+    pub notify: bool,
+    /// Keep the launcher open after the action (see `ProviderConfig::keep_open`)
+    #[serde(default)]
+    pub keep_open: bool,
+    /// Extra environment for the source and action commands (see `ProviderConfig::env`)
+    #[serde(default)]
+    pub env: HashMap<String, String>,
+    /// Record the query and the chosen result each time the action runs, for
+    /// `heats-list-history <evaluator>`
+    #[serde(default)]
+    pub history: bool,
 }
 
 /// A provider: source command + action command bundled together
 #[derive(Debug, Clone, Deserialize)]
 pub struct ProviderConfig {
     /// Source command + arguments (stdout に JSONL を出力)
+    #[serde(default)]
     pub source: Vec<String>,
+    /// Additional source commands run in parallel with `source`; their items are
+    /// concatenated in order and deduplicated by `field`
+    #[serde(default)]
+    pub sources: Vec<Vec<String>>,
     /// Action command + arguments (選択時に field 値を末尾に付与して実行)
     pub action: Vec<String>,
-    /// DmenuItem field to pass to the action (e.g. "data.path", "title"). Default: "data"
-    #[serde(default = "default_field")]
-    pub field: String,
+    /// Where the field value goes among the action's arguments. Default: append
+    #[serde(default)]
+    pub action_placement: ActionPlacement,
+    /// DmenuItem field(s) to pass to the action (e.g. "data.path", "title"), each as its
+    /// own argument in order. A single string or a list. Default: "data"
+    #[serde(default = "default_fields", deserialize_with = "one_or_many")]
+    pub field: Vec<String>,
     /// Background cache refresh interval in seconds. None = no caching (load on demand).
+    /// Ignored for `trigger = "on_query"` providers.
     pub cache_interval: Option<u64>,
+    /// When the source runs: when the mode opens (default) or on the first keystroke
+    #[serde(default)]
+    pub trigger: Trigger,
+    /// Run `source` as a shell command line via `sh -c` (pipelines, globs, `~`).
+    /// The words are joined with spaces and not escaped, so never build it from untrusted input.
+    #[serde(default)]
+    pub shell: bool,
+    /// Keep at most this many items from this provider (applied before merging)
+    pub max_items: Option<usize>,
+    /// Display title built from item fields, e.g. "{data.name}". Default: the item's title
+    pub title_template: Option<String>,
+    /// Display subtitle built from item fields, e.g. "{data.path}". Default: the item's subtitle
+    pub subtitle_template: Option<String>,
+    /// Post a macOS notification with the item's title after the action is spawned
+    #[serde(default)]
+    pub notify: bool,
+    /// Keep the launcher open after the action, clearing only the query (for repeatable actions)
+    #[serde(default)]
+    pub keep_open: bool,
+    /// Re-run a source command this many times (with a short backoff) when it fails
+    /// without producing any items. Default: 0
+    #[serde(default)]
+    pub retries: u32,
+    /// Extra environment for the source and action commands. Values may reference
+    /// heatsd's own environment as `$VAR` or `${VAR}` (e.g. "${PATH}:/opt/tools/bin").
+    #[serde(default)]
+    pub env: HashMap<String, String>,
+    /// Field shown in the preview pane (`window.preview`) instead of the whole item as JSON
+    pub preview_field: Option<String>,
+    /// Command whose stdout fills the preview pane, run with the selected item's field
+    /// values appended (e.g. ["bat", "--color=never"]). Takes precedence over `preview_field`.
+    #[serde(default)]
+    pub preview: Vec<String>,
+}
+
+/// When a provider's source command runs
+#[derive(Debug, Clone, Copy, Default, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum Trigger {
+    /// Load items when the mode is shown
+    #[default]
+    OnOpen,
+    /// Load items on the first non-empty query, passing the query on stdin
+    OnQuery,
+}
+
+impl ProviderConfig {
+    /// The item's values of each `field`, in order (the action's arguments).
+    pub fn field_values(&self, item: &DmenuItem) -> Vec<String> {
+        self.field
+            .iter()
+            .map(|field| item.get_field(field))
+            .collect()
+    }
+
+    /// The `field` values joined by spaces: the item's key for deduplication,
+    /// `pinned` and Cmd+C. Same as the value itself for a single field.
+    pub fn field_value(&self, item: &DmenuItem) -> String {
+        self.field_values(item).join(" ")
+    }
+
+    /// All source commands of the provider: `source` (if set) followed by `sources`.
+    pub fn source_commands(&self) -> Vec<&[String]> {
+        std::iter::once(self.source.as_slice())
+            .filter(|source| !source.is_empty())
+            .chain(self.sources.iter().map(Vec::as_slice))
+            .collect()
+    }
 }
 
 fn default_field() -> String {
     "data".to_string()
 }
 
+fn default_fields() -> Vec<String> {
+    vec![default_field()]
+}
+
+/// Accept `field = "data.path"` as well as `field = ["data.pid", "data.wid"]`
+fn one_or_many<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<String>, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum OneOrMany {
+        One(String),
+        Many(Vec<String>),
+    }
+    Ok(match OneOrMany::deserialize(deserializer)? {
+        OneOrMany::One(field) => vec![field],
+        OneOrMany::Many(fields) => fields,
+    })
+}
+
+fn default_debounce_ms() -> u64 {
+    100
+}
+
+fn default_min_chars() -> usize {
+    1
+}
+
 /// Window management mode
 #[derive(Debug, Clone, Deserialize, PartialEq)]
 #[serde(rename_all = "lowercase")]
@@ -85,29 +257,252 @@ pub enum WindowMode {
 #[serde(default)]
 pub struct WindowConfig {
     pub width: f32,
+    /// Window height; the maximum height when `auto_height` is on
     pub height: f32,
+    /// Shrink the window to fit the current results (down to the search box alone)
+    pub auto_height: bool,
     /// "normal" = follow mouse cursor, "fixed" = pin to a specific display
     pub mode: WindowMode,
     /// Display name for fixed mode (substring match, e.g. "LG" or "Built-in")
     pub display: String,
+    /// Show each row's source name as a dim right-aligned tag
+    pub show_source_tag: bool,
+    /// Friendly tag labels by source name (e.g. "open-apps" = "App").
+    /// "eval:*" matches every evaluator. Unmapped sources show their raw name.
+    pub source_labels: HashMap<String, String>,
+    /// Group results under a dim header per source (labels from `source_labels`)
+    pub group_by_source: bool,
+    /// Show a footer with the keys available in the current mode
+    pub show_hints: bool,
+    /// Hide the launcher after this many seconds without keyboard input. None = never.
+    /// Not applied while a dmenu session is waiting for a selection.
+    pub idle_timeout_secs: Option<u64>,
+    /// Frosted-glass (NSVisualEffectView) backing behind the launcher
+    pub blur: bool,
+    /// Hide the launcher when it loses focus (e.g. clicking another app). Default: true
+    pub hide_on_blur: bool,
+    /// Escape first clears a non-empty query and only hides on an empty one. Default: true
+    pub escape_clears_query: bool,
+    /// Vertical position of the window on its display. Default: third
+    pub anchor: WindowAnchor,
+    /// Vertical position as a fraction of the free space above the window
+    /// (0.0 = top, 0.5 = centered, 1.0 = bottom). Overrides `anchor` when set.
+    pub y_ratio: Option<f64>,
+    /// Fade-in / fade-out duration in ms (fixed mode). 0 disables the animation. Default: 120
+    pub fade_ms: u64,
+    /// Show a pane beside the results with the selected item's data
+    pub preview: bool,
+    /// Space in pixels above and below each result row's content. Default: 6
+    pub row_padding: f32,
+}
+
+/// Where the window sits vertically on its display
+#[derive(Debug, Clone, Copy, Default, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum WindowAnchor {
+    /// Near the top edge
+    Top,
+    /// A third of the way down (Spotlight-like)
+    #[default]
+    Third,
+    /// Vertically centered
+    Center,
+}
+
+impl WindowConfig {
+    /// Fraction of the display's free vertical space left above the window
+    pub fn y_ratio(&self) -> f64 {
+        match self.y_ratio {
+            Some(ratio) => ratio.clamp(0.0, 1.0),
+            None => match self.anchor {
+                WindowAnchor::Top => 0.1,
+                WindowAnchor::Third => 1.0 / 3.0,
+                WindowAnchor::Center => 0.5,
+            },
+        }
+    }
+}
+
+/// Fuzzy matching settings (`[matcher]`)
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+pub struct MatcherConfig {
+    /// Hide matches scoring below this (nucleo score, roughly 16 per matched character
+    /// plus bonuses). None = show every match. Not applied to an empty query.
+    pub min_score: Option<u32>,
+    /// Favor matches near the start of the title, so "saf" ranks "Safari" first
+    pub prefer_prefix: bool,
+    /// Score `/`-separated text like file paths, treating each path component as a word
+    pub match_paths: bool,
+    /// Case sensitivity of the query. Default: smart
+    pub case: CaseMode,
+    /// Match accented latin letters by their base letter ("e" finds "é"). Default: true
+    pub normalize: bool,
+}
+
+impl Default for MatcherConfig {
+    fn default() -> Self {
+        Self {
+            min_score: None,
+            prefer_prefix: false,
+            match_paths: false,
+            case: CaseMode::Smart,
+            normalize: true,
+        }
+    }
+}
+
+/// How the query's letter case is matched
+#[derive(Debug, Clone, Copy, Default, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum CaseMode {
+    /// Case-insensitive unless the query contains an uppercase letter
+    #[default]
+    Smart,
+    /// Always case-insensitive
+    Ignore,
+    /// Always case-sensitive
+    Respect,
+}
+
+/// Daemon logging (`[log]`). RUST_LOG, if set, still takes precedence over `level`.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct LogConfig {
+    /// A level ("debug", "info", ...) applied to heats itself, or a full tracing
+    /// filter such as "heats=debug,iced=warn". None = "info".
+    pub level: Option<String>,
+    /// Write logs to this file (appended, `~/` expanded) instead of stderr
+    pub file: Option<PathBuf>,
+}
+
+impl LogConfig {
+    /// tracing filter directives for the configured level
+    pub fn filter(&self) -> String {
+        match self.level.as_deref() {
+            None => "heats=info".to_string(),
+            Some(level @ ("trace" | "debug" | "info" | "warn" | "error")) => {
+                format!("heats={level}")
+            }
+            Some(directives) => directives.to_string(),
+        }
+    }
+
+    /// Log file path with a leading `~/` expanded
+    pub fn file_path(&self) -> Option<PathBuf> {
+        self.file.as_deref().map(expand_home)
+    }
+}
+
+/// Environment of spawned provider/evaluator commands (`[env]`)
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct EnvConfig {
+    /// Directories appended to PATH (`~/` expanded), after the login shell's PATH
+    pub path: Vec<PathBuf>,
+}
+
+impl EnvConfig {
+    /// `path` with a leading `~/` expanded
+    pub fn path_dirs(&self) -> Vec<PathBuf> {
+        self.path.iter().map(|dir| expand_home(dir)).collect()
+    }
+}
+
+/// Limits for item lists piped in through `heats` (`[dmenu]`)
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+pub struct DmenuConfig {
+    /// Keep at most this many input lines per session; the rest are dropped with a
+    /// warning. Default: 100000
+    pub max_items: usize,
+}
+
+impl Default for DmenuConfig {
+    fn default() -> Self {
+        Self { max_items: 100_000 }
+    }
+}
+
+/// Clipboard history recorded by heatsd and listed by `heats-clipboard` (`[clipboard]`)
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+pub struct ClipboardConfig {
+    /// Watch the pasteboard and keep a history of copied text. Default: false
+    pub history: bool,
+    /// How many entries the history keeps (oldest dropped first). Default: 50
+    pub max_entries: usize,
+}
+
+impl Default for ClipboardConfig {
+    fn default() -> Self {
+        Self {
+            history: false,
+            max_entries: 50,
+        }
+    }
+}
+
+/// Expand a leading `~/` to the home directory
+fn expand_home(path: &std::path::Path) -> PathBuf {
+    match (path.strip_prefix("~"), dirs::home_dir()) {
+        (Ok(rest), Some(home)) => home.join(rest),
+        _ => path.to_path_buf(),
+    }
+}
+
+impl Config {
+    /// Problems that don't stop the config from loading, such as a mode listing a
+    /// provider or evaluator that isn't defined. Empty when everything checks out.
+    pub fn validate(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+        for mode in &self.mode {
+            for name in &mode.providers {
+                if !self.provider.contains_key(name) {
+                    problems.push(format!(
+                        "mode '{}' references unknown provider '{name}'",
+                        mode.name
+                    ));
+                }
+            }
+            for name in &mode.evaluators {
+                if !self.evaluator.contains_key(name) {
+                    problems.push(format!(
+                        "mode '{}' references unknown evaluator '{name}'",
+                        mode.name
+                    ));
+                }
+            }
+        }
+        problems
+    }
 }
 
 impl Default for Config {
     fn default() -> Self {
         Self {
             window: WindowConfig::default(),
+            matcher: MatcherConfig::default(),
+            log: LogConfig::default(),
+            env: EnvConfig::default(),
+            dmenu: DmenuConfig::default(),
+            clipboard: ClipboardConfig::default(),
             mode: vec![
                 ModeConfig {
                     name: "launcher".to_string(),
                     hotkey: "Cmd+Semicolon".to_string(),
                     providers: vec!["open-apps".to_string(), "focus-window".to_string()],
-                    evaluators: vec!["calculator".to_string()],
+                    evaluators: vec!["calculator".to_string(), "units".to_string()],
+                    pinned: Vec::new(),
+                    icon: None,
                 },
                 ModeConfig {
                     name: "windows".to_string(),
                     hotkey: "Cmd+Quote".to_string(),
                     providers: vec!["focus-window".to_string()],
                     evaluators: Vec::new(),
+                    pinned: Vec::new(),
+                    icon: None,
                 },
             ],
             provider: HashMap::from([
@@ -115,31 +510,127 @@ impl Default for Config {
                     "open-apps".to_string(),
                     ProviderConfig {
                         source: vec!["heats-list-apps".to_string()],
+                        sources: Vec::new(),
                         action: vec!["open".to_string(), "-a".to_string()],
-                        field: "data.path".to_string(),
+                        action_placement: ActionPlacement::Append,
+                        field: vec!["data.path".to_string()],
                         cache_interval: None,
+                        trigger: Trigger::OnOpen,
+                        shell: false,
+                        max_items: None,
+                        retries: 0,
+                        title_template: None,
+                        subtitle_template: None,
+                        notify: false,
+                        keep_open: false,
+                        env: HashMap::new(),
+                        preview_field: None,
+                        preview: Vec::new(),
                     },
                 ),
                 (
                     "focus-window".to_string(),
                     ProviderConfig {
                         source: vec!["heats-list-windows".to_string()],
+                        sources: Vec::new(),
                         action: vec!["heats-focus-window".to_string()],
-                        field: "data.pid".to_string(),
+                        action_placement: ActionPlacement::Append,
+                        field: vec!["data.pid".to_string()],
                         cache_interval: None,
+                        trigger: Trigger::OnOpen,
+                        shell: false,
+                        max_items: None,
+                        retries: 0,
+                        title_template: None,
+                        subtitle_template: None,
+                        notify: false,
+                        keep_open: false,
+                        env: HashMap::new(),
+                        preview_field: None,
+                        preview: Vec::new(),
                     },
                 ),
             ]),
-            evaluator: HashMap::from([(
-                "calculator".to_string(),
-                EvaluatorConfig {
-                    source: vec!["heats-eval-calc".to_string()],
-                    input: InputMode::default(),
-                    action: vec!["pbcopy".to_string()],
-                    action_input: InputMode::default(),
-                    field: "data".to_string(),
-                },
-            )]),
+            evaluator: HashMap::from([
+                (
+                    "calculator".to_string(),
+                    EvaluatorConfig {
+                        source: vec!["heats-eval-calc".to_string()],
+                        input: InputMode::default(),
+                        action: vec!["pbcopy".to_string()],
+                        action_input: InputMode::default(),
+                        action_placement: ActionPlacement::Append,
+                        field: "data".to_string(),
+                        debounce_ms: default_debounce_ms(),
+                        min_chars: default_min_chars(),
+                        prefix: None,
+                        shell: false,
+                        notify: false,
+                        keep_open: false,
+                        env: HashMap::new(),
+                        history: false,
+                    },
+                ),
+                (
+                    "units".to_string(),
+                    EvaluatorConfig {
+                        source: vec!["heats-eval-units".to_string()],
+                        input: InputMode::default(),
+                        action: vec!["pbcopy".to_string()],
+                        action_input: InputMode::default(),
+                        action_placement: ActionPlacement::Append,
+                        field: "data".to_string(),
+                        debounce_ms: default_debounce_ms(),
+                        min_chars: default_min_chars(),
+                        prefix: None,
+                        shell: false,
+                        notify: false,
+                        keep_open: false,
+                        env: HashMap::new(),
+                        history: false,
+                    },
+                ),
+                (
+                    "date".to_string(),
+                    EvaluatorConfig {
+                        source: vec!["heats-eval-date".to_string()],
+                        input: InputMode::default(),
+                        action: vec!["pbcopy".to_string()],
+                        action_input: InputMode::default(),
+                        action_placement: ActionPlacement::Append,
+                        field: "data".to_string(),
+                        debounce_ms: default_debounce_ms(),
+                        min_chars: default_min_chars(),
+                        prefix: None,
+                        shell: false,
+                        notify: false,
+                        keep_open: false,
+                        env: HashMap::new(),
+                        history: false,
+                    },
+                ),
+                (
+                    "emoji".to_string(),
+                    EvaluatorConfig {
+                        source: vec!["heats-eval-emoji".to_string()],
+                        input: InputMode::default(),
+                        action: vec!["pbcopy".to_string()],
+                        action_input: InputMode::default(),
+                        action_placement: ActionPlacement::Append,
+                        field: "data".to_string(),
+                        debounce_ms: default_debounce_ms(),
+                        min_chars: default_min_chars(),
+                        prefix: None,
+                        shell: false,
+                        notify: false,
+                        keep_open: false,
+                        env: HashMap::new(),
+                        history: false,
+                    },
+                ),
+            ]),
+            on_show: None,
+            on_hide: None,
         }
     }
 }
@@ -149,12 +640,44 @@ impl Default for WindowConfig {
         Self {
             width: 600.0,
             height: 400.0,
+            auto_height: false,
             mode: WindowMode::Normal,
             display: String::new(),
+            show_source_tag: false,
+            source_labels: HashMap::new(),
+            group_by_source: false,
+            show_hints: false,
+            idle_timeout_secs: None,
+            blur: false,
+            hide_on_blur: true,
+            escape_clears_query: true,
+            anchor: WindowAnchor::Third,
+            y_ratio: None,
+            fade_ms: 120,
+            preview: false,
+            row_padding: 6.0,
+        }
+    }
+}
+
+/// Error returned when an existing config file cannot be read or parsed
+#[derive(Debug)]
+pub enum ConfigError {
+    Read(std::io::Error),
+    Parse(toml::de::Error),
+}
+
+impl std::fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Read(e) => write!(f, "failed to read config file: {e}"),
+            Self::Parse(e) => write!(f, "failed to parse config: {e}"),
         }
     }
 }
 
+impl std::error::Error for ConfigError {}
+
 pub fn load_from(path: &std::path::Path) -> Config {
     let path = path.to_path_buf();
     load_path(&path)
@@ -165,30 +688,40 @@ pub fn load() -> Config {
     load_path(&path)
 }
 
+/// Read and parse a config file, returning the error instead of falling back to defaults.
+pub fn try_load_from(path: &std::path::Path) -> Result<Config, ConfigError> {
+    let contents = std::fs::read_to_string(path).map_err(ConfigError::Read)?;
+    toml::from_str(&contents).map_err(ConfigError::Parse)
+}
+
 fn load_path(path: &PathBuf) -> Config {
+    load_reporting(path).0
+}
+
+/// Like `load_from`, but also returns the error when an existing file could not be
+/// loaded (the returned config is then the default).
+pub fn load_reporting(path: &std::path::Path) -> (Config, Option<ConfigError>) {
     if !path.exists() {
         tracing::info!("No config file found at {:?}, using defaults", path);
-        return Config::default();
+        return (Config::default(), None);
     }
-    match std::fs::read_to_string(&path) {
-        Ok(contents) => match toml::from_str(&contents) {
-            Ok(config) => {
-                tracing::info!("Loaded config from {:?}", path);
-                config
+    match try_load_from(path) {
+        Ok(config) => {
+            tracing::info!("Loaded config from {:?}", path);
+            for problem in config.validate() {
+                tracing::warn!("Config: {problem}");
             }
-            Err(e) => {
-                tracing::warn!("Failed to parse config: {}, using defaults", e);
-                Config::default()
-            }
-        },
+            (config, None)
+        }
         Err(e) => {
-            tracing::warn!("Failed to read config file: {}, using defaults", e);
-            Config::default()
+            tracing::warn!("{}, using defaults", e);
+            (Config::default(), Some(e))
         }
     }
 }
 
-fn config_path() -> PathBuf {
+/// Default config file location (`~/.config/heats/config.toml`)
+pub fn config_path() -> PathBuf {
     // Use ~/.config/ (XDG convention) instead of ~/Library/Application Support/ (macOS default)
     dirs::home_dir()
         .expect("Could not determine home directory")
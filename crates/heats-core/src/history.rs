@@ -0,0 +1,52 @@
+use std::path::PathBuf;
+
+use crate::cache::{read_json_list, unix_now, write_json};
+
+/// How many results each evaluator's history keeps
+const MAX_ENTRIES: usize = 100;
+
+/// An evaluator result chosen through heats, as stored in its history
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct HistoryEntry {
+    /// The query that produced the result (e.g. "2^10")
+    pub query: String,
+    /// The result row's title (e.g. "= 1024")
+    pub title: String,
+    /// The value handed to the action (e.g. "1024")
+    pub value: String,
+    /// When it was chosen (seconds since the Unix epoch)
+    pub chosen_at: u64,
+}
+
+/// History file of an evaluator (`~/.cache/heats/history/<evaluator>.json`)
+pub fn history_file(evaluator: &str) -> PathBuf {
+    dirs::home_dir()
+        .expect("Could not determine home directory")
+        .join(".cache")
+        .join("heats")
+        .join("history")
+        .join(format!("{evaluator}.json"))
+}
+
+/// Read an evaluator's history, most recent first. Empty if missing or unreadable.
+pub fn read(evaluator: &str) -> Vec<HistoryEntry> {
+    read_json_list(&history_file(evaluator))
+}
+
+/// Put a result at the front of the evaluator's history (replacing an earlier entry
+/// for the same query) and save it.
+pub fn record(evaluator: &str, query: &str, title: &str, value: &str) {
+    let mut entries = read(evaluator);
+    entries.retain(|entry| entry.query != query);
+    entries.insert(
+        0,
+        HistoryEntry {
+            query: query.to_string(),
+            title: title.to_string(),
+            value: value.to_string(),
+            chosen_at: unix_now(),
+        },
+    );
+    entries.truncate(MAX_ENTRIES);
+    write_json(&history_file(evaluator), &entries);
+}
@@ -1,9 +1,103 @@
 use std::path::PathBuf;
 
+/// IPC context sent as the first line by the client
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+pub struct IpcContext {
+    /// Item format: "text" or "jsonl"
+    pub format: String,
+    /// How the selected item is reported back to the client
+    #[serde(default)]
+    pub response: ResponseFormat,
+    /// Accept the selection as soon as a non-empty query narrows the items to one
+    #[serde(default)]
+    pub auto_select: bool,
+    /// Initial query the picker opens with
+    #[serde(default)]
+    pub query: Option<String>,
+    /// Cancel the session if nothing is selected within this many milliseconds
+    #[serde(default)]
+    pub timeout_ms: Option<u64>,
+    /// Placeholder shown in the empty search input
+    #[serde(default)]
+    pub prompt: Option<String>,
+    /// Glyph shown at the left of the search input (first character used)
+    #[serde(default)]
+    pub icon: Option<String>,
+    /// Allow marking several items with Tab; each is reported on its own line
+    #[serde(default)]
+    pub multi: bool,
+    /// Mask the typed query (e.g. for passphrase prompts)
+    #[serde(default)]
+    pub password: bool,
+    /// Open the session right after the context line and append items as their lines
+    /// arrive, until EOF (for "watch" style pickers)
+    #[serde(default)]
+    pub live: bool,
+    /// Report each selection as a JSON object of these item fields (e.g. "title",
+    /// "data.pid"), resolved like a provider `field`. Overrides `response` when set.
+    #[serde(default)]
+    pub fields: Vec<String>,
+    /// Put the chosen action's name ([`ACTION_DEFAULT`] or [`ACTION_ALT`]) on its own
+    /// line before the selection
+    #[serde(default)]
+    pub report_action: bool,
+}
+
+/// Action reported for a selection made with Enter (or a click)
+pub const ACTION_DEFAULT: &str = "default";
+/// Action reported for a selection made with Alt+Enter (or Alt+click)
+pub const ACTION_ALT: &str = "alt";
+
+/// Control request sent as the first line instead of an [`IpcContext`].
+/// The daemon answers with an [`IpcReply`] line and closes the connection.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "op", rename_all = "lowercase")]
+pub enum IpcCommand {
+    /// Health check (`{"op":"ping"}`), answered without involving the UI
+    Ping,
+    /// Open the launcher in a mode, like its hotkey (`{"op":"show","mode":"launcher"}`)
+    Show { mode: String },
+    /// Close the launcher (`{"op":"hide"}`)
+    Hide,
+}
+
+/// Reply to an [`IpcCommand`]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct IpcReply {
+    pub ok: bool,
+}
+
+/// How the daemon reports the selected item back to the client
+#[derive(Debug, Clone, Copy, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ResponseFormat {
+    /// The selected raw line (default)
+    #[default]
+    Line,
+    /// The original 0-based index and the raw line, separated by a tab.
+    /// Lets scripts tell duplicate lines apart.
+    Indexed,
+    /// Only the original 0-based index (for correlating with parallel arrays)
+    Index,
+    /// A single-line JSON object (see [`IpcResponse`])
+    Json,
+}
+
+/// Selection reported back to the client for `ResponseFormat::Json`
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct IpcResponse {
+    /// Original 0-based index of the selected line
+    pub index: usize,
+    /// The selected raw line
+    pub value: String,
+    /// Name of the chosen action ([`ACTION_DEFAULT`] or [`ACTION_ALT`])
+    pub action: String,
+}
+
 /// Resolve the runtime directory for IPC files.
 /// Uses $XDG_RUNTIME_DIR, falling back to /tmp/heats-{uid}.
 /// Creates the directory if it does not exist.
-fn runtime_dir() -> PathBuf {
+pub(crate) fn runtime_dir() -> PathBuf {
     let dir = if let Ok(dir) = std::env::var("XDG_RUNTIME_DIR") {
         PathBuf::from(dir)
     } else {
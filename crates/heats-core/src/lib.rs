@@ -1,4 +1,8 @@
+pub mod cache;
+pub mod clipboard;
 pub mod config;
+pub mod history;
 pub mod ipc;
 pub mod platform;
+pub mod recent;
 pub mod source;
@@ -23,6 +23,12 @@ pub fn ensure_screen_capture_access() {
     }
 }
 
+/// Check whether Screen Recording permission is granted, without prompting.
+/// Window titles from CGWindowListCopyWindowInfo are empty without it.
+pub fn screen_capture_access_granted() -> bool {
+    unsafe { CGPreflightScreenCaptureAccess() }
+}
+
 /// Force-activate this application, bringing it to the foreground.
 /// Used after subprocess spawning which may steal macOS app activation.
 pub fn activate_app() {
@@ -38,6 +44,104 @@ pub fn open_application(path: &str) -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+/// Replace the general pasteboard's contents with `text` (as a plain string).
+pub fn copy_to_clipboard(text: &str) {
+    let Ok(c_text) = std::ffi::CString::new(text) else {
+        tracing::warn!("copy_to_clipboard: text contains a NUL byte");
+        return;
+    };
+    unsafe {
+        let pasteboard: *mut Object = msg_send![class!(NSPasteboard), generalPasteboard];
+        let ns_text: *mut Object =
+            msg_send![class!(NSString), stringWithUTF8String: c_text.as_ptr()];
+        // NSPasteboardTypeString
+        let string_type: *mut Object =
+            msg_send![class!(NSString), stringWithUTF8String: c"public.utf8-plain-text".as_ptr()];
+        let _: isize = msg_send![pasteboard, clearContents];
+        let _: objc::runtime::BOOL = msg_send![pasteboard, setString: ns_text forType: string_type];
+    }
+}
+
+/// The general pasteboard's change count, bumped by every copy in any app
+pub fn pasteboard_change_count() -> isize {
+    unsafe {
+        let pasteboard: *mut Object = msg_send![class!(NSPasteboard), generalPasteboard];
+        msg_send![pasteboard, changeCount]
+    }
+}
+
+/// The general pasteboard's contents as plain text, if it holds any
+pub fn pasteboard_string() -> Option<String> {
+    unsafe {
+        let pasteboard: *mut Object = msg_send![class!(NSPasteboard), generalPasteboard];
+        // NSPasteboardTypeString
+        let string_type: *mut Object =
+            msg_send![class!(NSString), stringWithUTF8String: c"public.utf8-plain-text".as_ptr()];
+        let ns_text: *mut Object = msg_send![pasteboard, stringForType: string_type];
+        if ns_text.is_null() {
+            return None;
+        }
+        let c_str: *const i8 = msg_send![ns_text, UTF8String];
+        if c_str.is_null() {
+            return None;
+        }
+        Some(
+            std::ffi::CStr::from_ptr(c_str)
+                .to_string_lossy()
+                .into_owned(),
+        )
+    }
+}
+
+/// Pasteboard types that mark a copy as not to be recorded (nspasteboard.org),
+/// set by password managers and similar apps
+const CONCEALED_PASTEBOARD_TYPES: [&str; 2] = [
+    "org.nspasteboard.ConcealedType",
+    "org.nspasteboard.TransientType",
+];
+
+/// Whether the general pasteboard's current contents are marked concealed or transient
+pub fn pasteboard_is_concealed() -> bool {
+    unsafe {
+        let pasteboard: *mut Object = msg_send![class!(NSPasteboard), generalPasteboard];
+        let types: *mut Object = msg_send![pasteboard, types];
+        if types.is_null() {
+            return false;
+        }
+        let count: usize = msg_send![types, count];
+        (0..count).any(|i| {
+            let pb_type: *mut Object = msg_send![types, objectAtIndex: i];
+            if pb_type.is_null() {
+                return false;
+            }
+            let c_str: *const i8 = msg_send![pb_type, UTF8String];
+            !c_str.is_null()
+                && std::ffi::CStr::from_ptr(c_str)
+                    .to_str()
+                    .is_ok_and(|t| CONCEALED_PASTEBOARD_TYPES.contains(&t))
+        })
+    }
+}
+
+/// Post a user notification (fire-and-forget).
+/// Goes through `osascript` because heatsd usually runs outside an app bundle,
+/// which the UserNotifications framework requires.
+pub fn post_notification(title: &str, message: &str) {
+    let script = format!(
+        "display notification {} with title {}",
+        applescript_string(message),
+        applescript_string(title)
+    );
+    if let Err(e) = Command::new("osascript").arg("-e").arg(script).spawn() {
+        tracing::warn!("Failed to post notification: {}", e);
+    }
+}
+
+/// Quote a string as an AppleScript string literal.
+fn applescript_string(s: &str) -> String {
+    format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
 /// Activate the application with the given PID, bringing its windows to front.
 pub fn focus_window(pid: i32) {
     unsafe {
@@ -113,6 +217,38 @@ pub fn focused_display_bounds() -> (f64, f64, f64, f64) {
     fallback_main_display()
 }
 
+/// Get the bounds of the display containing the mouse cursor (CG coordinates).
+/// Falls back to the keyboard-focused display if no active display contains the cursor.
+pub fn mouse_display_bounds() -> (f64, f64, f64, f64) {
+    let (mouse_x, mouse_y) = mouse_position();
+    let display_ids = CGDisplay::active_displays().unwrap_or_default();
+
+    for display_id in display_ids {
+        let bounds = CGDisplay::new(display_id).bounds();
+        let contains_x =
+            mouse_x >= bounds.origin.x && mouse_x < bounds.origin.x + bounds.size.width;
+        let contains_y =
+            mouse_y >= bounds.origin.y && mouse_y < bounds.origin.y + bounds.size.height;
+        if contains_x && contains_y {
+            tracing::debug!(
+                "mouse_display_bounds: mouse=({}, {}) → CGDisplayID={}",
+                mouse_x,
+                mouse_y,
+                display_id
+            );
+            return (
+                bounds.origin.x,
+                bounds.origin.y,
+                bounds.size.width,
+                bounds.size.height,
+            );
+        }
+    }
+
+    tracing::debug!("mouse_display_bounds: no display contains the cursor, falling back");
+    focused_display_bounds()
+}
+
 /// Extract CGDirectDisplayID from an NSScreen via deviceDescription["NSScreenNumber"].
 unsafe fn screen_display_id(screen: *mut Object) -> Option<u32> {
     let desc: *mut Object = msg_send![screen, deviceDescription];
@@ -128,8 +264,9 @@ unsafe fn screen_display_id(screen: *mut Object) -> Option<u32> {
 }
 
 /// Get the bounds of a display by name (substring match).
-/// Returns CG coordinates (origin at top-left of main display).
-pub fn display_bounds_by_name(name: &str) -> (f64, f64, f64, f64) {
+/// Returns CG coordinates (origin at top-left of main display),
+/// or None if no connected screen matches (e.g. the monitor was unplugged).
+pub fn display_bounds_by_name(name: &str) -> Option<(f64, f64, f64, f64)> {
     let screens = list_screens();
     let name_lower = name.to_lowercase();
 
@@ -146,12 +283,12 @@ pub fn display_bounds_by_name(name: &str) -> (f64, f64, f64, f64) {
                 bounds.size.width,
                 bounds.size.height
             );
-            return (
+            return Some((
                 bounds.origin.x,
                 bounds.origin.y,
                 bounds.size.width,
                 bounds.size.height,
-            );
+            ));
         }
     }
 
@@ -161,7 +298,25 @@ pub fn display_bounds_by_name(name: &str) -> (f64, f64, f64, f64) {
         name,
         available
     );
-    fallback_main_display()
+    None
+}
+
+/// Largest backing scale factor among connected screens (2.0 with a Retina display).
+pub fn max_backing_scale_factor() -> f64 {
+    let mut max_scale: f64 = 1.0;
+
+    unsafe {
+        let screens: *mut Object = msg_send![class!(NSScreen), screens];
+        let count: usize = msg_send![screens, count];
+
+        for i in 0..count {
+            let screen: *mut Object = msg_send![screens, objectAtIndex: i];
+            let scale: f64 = msg_send![screen, backingScaleFactor];
+            max_scale = max_scale.max(scale);
+        }
+    }
+
+    max_scale
 }
 
 /// List all screens: (name, CGDirectDisplayID).
@@ -271,14 +426,22 @@ unsafe fn find_heats_window() -> Option<*mut Object> {
     None
 }
 
-/// Show the Heats window at the center of the given display (CG coordinates).
-/// Uses NSWindow.setFrame + makeKeyAndOrderFront (like Raycast).
-pub fn native_show_window(display: &(f64, f64, f64, f64), win_w: f64, win_h: f64) {
+/// Show the Heats window horizontally centered on the given display (CG coordinates),
+/// with `y_ratio` of the free vertical space above it (e.g. 1/3).
+/// Uses NSWindow.setFrame + makeKeyAndOrderFront (like Raycast), fading in over `fade_ms`
+/// (0 = appear immediately).
+pub fn native_show_window(
+    display: &(f64, f64, f64, f64),
+    win_w: f64,
+    win_h: f64,
+    y_ratio: f64,
+    fade_ms: u64,
+) {
     let (disp_x, disp_y, disp_w, disp_h) = *display;
 
-    // Center position in CG coordinates (origin = top-left of main display, y down)
+    // Position in CG coordinates (origin = top-left of main display, y down)
     let cg_x = disp_x + (disp_w - win_w) / 2.0;
-    let cg_y = disp_y + (disp_h - win_h) / 3.0;
+    let cg_y = disp_y + (disp_h - win_h) * y_ratio;
 
     // Convert CG → AppKit coordinates (origin = bottom-left of main display, y up)
     let main_height = CGDisplay::main().bounds().size.height;
@@ -299,13 +462,104 @@ pub fn native_show_window(display: &(f64, f64, f64, f64), win_w: f64, win_h: f64
             let display_flag: i8 = 1; // YES
             let animate_flag: i8 = 0; // NO
             let _: () = msg_send![window, setFrame:frame display:display_flag animate:animate_flag];
-            let _: () = msg_send![window, makeKeyAndOrderFront: std::ptr::null::<Object>()];
+
+            // A fade-out from the previous hide may still have its orderOut pending
+            let _: () = msg_send![
+                class!(NSObject),
+                cancelPreviousPerformRequestsWithTarget: window
+                selector: sel!(orderOut:)
+                object: std::ptr::null::<Object>()
+            ];
+            if fade_ms == 0 {
+                let _: () = msg_send![window, setAlphaValue: 1.0f64];
+                let _: () = msg_send![window, makeKeyAndOrderFront: std::ptr::null::<Object>()];
+            } else {
+                let _: () = msg_send![window, setAlphaValue: 0.0f64];
+                let _: () = msg_send![window, makeKeyAndOrderFront: std::ptr::null::<Object>()];
+                animate_alpha(window, 1.0, fade_ms);
+            }
         } else {
             tracing::warn!("native_show_window: Heats window not found");
         }
     }
 }
 
+/// NSVisualEffectMaterialHUDWindow
+const VISUAL_EFFECT_MATERIAL_HUD_WINDOW: isize = 13;
+/// NSVisualEffectBlendingModeBehindWindow
+const VISUAL_EFFECT_BLENDING_BEHIND_WINDOW: isize = 0;
+/// NSVisualEffectStateActive (stay blurred even when Heats is not the key window)
+const VISUAL_EFFECT_STATE_ACTIVE: isize = 1;
+/// NSViewWidthSizable | NSViewHeightSizable
+const AUTORESIZE_WIDTH_HEIGHT: usize = 2 | 16;
+/// NSWindowBelow
+const WINDOW_BELOW: isize = -1;
+
+/// Install an NSVisualEffectView behind the Heats window's content for a frosted-glass look.
+/// The content view draws into its own (Metal) layer, so the effect view is added to the
+/// window frame view below it rather than as a subview. `corner_radius` should match the
+/// launcher container so the blur does not show past its rounded corners.
+pub fn install_window_blur(corner_radius: f64) {
+    unsafe {
+        let Some(window) = find_heats_window() else {
+            tracing::warn!("install_window_blur: Heats window not found");
+            return;
+        };
+        let content_view: *mut Object = msg_send![window, contentView];
+        if content_view.is_null() {
+            return;
+        }
+        let frame_view: *mut Object = msg_send![content_view, superview];
+        if frame_view.is_null() {
+            return;
+        }
+
+        let frame: NSRect = msg_send![content_view, frame];
+        let effect_view: *mut Object = msg_send![class!(NSVisualEffectView), alloc];
+        let effect_view: *mut Object = msg_send![effect_view, initWithFrame: frame];
+        let _: () = msg_send![effect_view, setMaterial: VISUAL_EFFECT_MATERIAL_HUD_WINDOW];
+        let _: () = msg_send![effect_view, setBlendingMode: VISUAL_EFFECT_BLENDING_BEHIND_WINDOW];
+        let _: () = msg_send![effect_view, setState: VISUAL_EFFECT_STATE_ACTIVE];
+        let _: () = msg_send![effect_view, setAutoresizingMask: AUTORESIZE_WIDTH_HEIGHT];
+
+        let _: () = msg_send![effect_view, setWantsLayer: true];
+        let layer: *mut Object = msg_send![effect_view, layer];
+        if !layer.is_null() {
+            let _: () = msg_send![layer, setCornerRadius: corner_radius];
+            let _: () = msg_send![layer, setMasksToBounds: true];
+        }
+
+        let _: () = msg_send![frame_view, addSubview: effect_view positioned: WINDOW_BELOW relativeTo: content_view];
+        tracing::debug!("install_window_blur: NSVisualEffectView installed");
+    }
+}
+
+/// Resize the Heats window, keeping its top edge in place
+/// (AppKit frames grow upward from the bottom-left origin).
+pub fn native_resize_window(win_w: f64, win_h: f64) {
+    unsafe {
+        if let Some(window) = find_heats_window() {
+            let current: NSRect = msg_send![window, frame];
+            let top = current.origin.y + current.size.height;
+            let frame = NSRect {
+                origin: NSPoint {
+                    x: current.origin.x,
+                    y: top - win_h,
+                },
+                size: NSSize {
+                    width: win_w,
+                    height: win_h,
+                },
+            };
+            let display_flag: i8 = 1; // YES
+            let animate_flag: i8 = 0; // NO
+            let _: () = msg_send![window, setFrame:frame display:display_flag animate:animate_flag];
+        } else {
+            tracing::warn!("native_resize_window: Heats window not found");
+        }
+    }
+}
+
 /// Force the Heats window to become key window and bring to front.
 /// This ensures macOS routes keyboard events to the window.
 pub fn native_focus_heats_window() {
@@ -321,22 +575,45 @@ pub fn native_focus_heats_window() {
     }
 }
 
-/// Hide the Heats window using NSWindow.orderOut (Raycast-style).
-pub fn native_hide_window() {
+/// Hide the Heats window using NSWindow.orderOut (Raycast-style), fading out over
+/// `fade_ms` first (0 = hide immediately).
+pub fn native_hide_window(fade_ms: u64) {
     unsafe {
         if let Some(window) = find_heats_window() {
-            let _: () = msg_send![window, orderOut: std::ptr::null::<Object>()];
+            if fade_ms == 0 {
+                let _: () = msg_send![window, orderOut: std::ptr::null::<Object>()];
+                return;
+            }
+            animate_alpha(window, 0.0, fade_ms);
+            // orderOut once the fade has finished; cancelled by native_show_window
+            // if the launcher is reopened in the meantime
+            let delay = fade_ms as f64 / 1000.0;
+            let _: () = msg_send![
+                window,
+                performSelector: sel!(orderOut:)
+                withObject: std::ptr::null::<Object>()
+                afterDelay: delay
+            ];
         }
     }
 }
 
+/// Animate the window's alphaValue to `alpha` through NSAnimationContext.
+unsafe fn animate_alpha(window: *mut Object, alpha: f64, duration_ms: u64) {
+    let _: () = msg_send![class!(NSAnimationContext), beginGrouping];
+    let context: *mut Object = msg_send![class!(NSAnimationContext), currentContext];
+    let _: () = msg_send![context, setDuration: duration_ms as f64 / 1000.0];
+    let animator: *mut Object = msg_send![window, animator];
+    let _: () = msg_send![animator, setAlphaValue: alpha];
+    let _: () = msg_send![class!(NSAnimationContext), endGrouping];
+}
+
 fn fallback_main_display() -> (f64, f64, f64, f64) {
     let main = CGDisplay::main();
     let b = main.bounds();
     (b.origin.x, b.origin.y, b.size.width, b.size.height)
 }
 
-#[allow(dead_code)]
 fn mouse_position() -> (f64, f64) {
     let source = match core_graphics::event_source::CGEventSource::new(
         core_graphics::event_source::CGEventSourceStateID::CombinedSessionState,
@@ -0,0 +1,44 @@
+use std::path::PathBuf;
+
+use crate::cache::{read_json_list, unix_now, write_json};
+
+/// How many apps the list keeps
+const MAX_ENTRIES: usize = 50;
+
+/// An app launched through heats, as stored in the recent list
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct RecentApp {
+    /// Path of the `.app` bundle
+    pub path: String,
+    /// When it was last launched (seconds since the Unix epoch)
+    pub launched_at: u64,
+}
+
+/// Recent apps file (`~/.cache/heats/recent-apps.json`)
+pub fn recent_file() -> PathBuf {
+    dirs::home_dir()
+        .expect("Could not determine home directory")
+        .join(".cache")
+        .join("heats")
+        .join("recent-apps.json")
+}
+
+/// Read the recent apps, most recently launched first. Empty if missing or unreadable.
+pub fn read() -> Vec<RecentApp> {
+    read_json_list(&recent_file())
+}
+
+/// Move `app_path` to the front of the recent list (adding it if new) and save it.
+pub fn record(app_path: &str) {
+    let mut apps = read();
+    apps.retain(|app| app.path != app_path);
+    apps.insert(
+        0,
+        RecentApp {
+            path: app_path.to_string(),
+            launched_at: unix_now(),
+        },
+    );
+    apps.truncate(MAX_ENTRIES);
+    write_json(&recent_file(), &apps);
+}
@@ -1,4 +1,4 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 /// Directories to scan for macOS applications
 const APP_DIRS: &[&str] = &[
@@ -12,11 +12,14 @@ const APP_DIRS: &[&str] = &[
 pub struct AppEntry {
     pub name: String,
     pub path: String,
+    /// `CFBundleShortVersionString` from the bundle's Info.plist (e.g. "17.4")
+    pub version: Option<String>,
 }
 
-/// Scan standard macOS directories for .app bundles.
+/// Scan standard macOS directories for .app bundles, skipping those whose name or path
+/// matches one of the `exclude` patterns (see `matches_pattern`).
 /// Returns a sorted list of AppEntry without loading icons.
-pub fn scan_apps() -> Vec<AppEntry> {
+pub fn scan_apps(exclude: &[String]) -> Vec<AppEntry> {
     let mut items = Vec::new();
     for dir in APP_DIRS {
         let path = PathBuf::from(dir);
@@ -35,9 +38,17 @@ pub fn scan_apps() -> Vec<AppEntry> {
                     .unwrap_or_default()
                     .to_string_lossy()
                     .to_string();
+                let path_str = path.to_string_lossy().to_string();
+                let excluded = exclude.iter().any(|pattern| {
+                    matches_pattern(pattern, &name) || matches_pattern(pattern, &path_str)
+                });
+                if excluded {
+                    continue;
+                }
                 items.push(AppEntry {
                     name,
-                    path: path.to_string_lossy().to_string(),
+                    version: bundle_version(&path),
+                    path: path_str,
                 });
             }
         }
@@ -45,3 +56,43 @@ pub fn scan_apps() -> Vec<AppEntry> {
     items.sort_by(|a, b| a.name.cmp(&b.name));
     items
 }
+
+/// Read `CFBundleShortVersionString` from the app's Info.plist.
+fn bundle_version(app_path: &Path) -> Option<String> {
+    info_plist_string(app_path, "CFBundleShortVersionString")
+}
+
+/// Read `CFBundleIdentifier` (e.g. "com.apple.Safari") from the app's Info.plist.
+pub fn bundle_identifier(app_path: &Path) -> Option<String> {
+    info_plist_string(app_path, "CFBundleIdentifier")
+}
+
+fn info_plist_string(app_path: &Path, key: &str) -> Option<String> {
+    let plist = plist::Value::from_file(app_path.join("Contents/Info.plist")).ok()?;
+    plist
+        .as_dictionary()
+        .and_then(|dict| dict.get(key))
+        .and_then(|val| val.as_string())
+        .map(|s| s.to_string())
+}
+
+/// Match `text` against an exclude pattern. `*` matches any run of characters and the
+/// pattern must cover the whole text; a pattern without `*` matches as a substring.
+fn matches_pattern(pattern: &str, text: &str) -> bool {
+    if !pattern.contains('*') {
+        return text.contains(pattern);
+    }
+    let parts: Vec<&str> = pattern.split('*').collect();
+    let (first, last) = (parts[0], parts[parts.len() - 1]);
+    if text.len() < first.len() + last.len() || !text.starts_with(first) || !text.ends_with(last) {
+        return false;
+    }
+    let mut rest = &text[first.len()..text.len() - last.len()];
+    for part in &parts[1..parts.len() - 1] {
+        match rest.find(part) {
+            Some(pos) => rest = &rest[pos + part.len()..],
+            None => return false,
+        }
+    }
+    true
+}
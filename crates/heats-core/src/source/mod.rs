@@ -1,4 +1,5 @@
 pub mod applications;
+pub mod tabs;
 pub mod windows;
 
 use std::sync::Arc;
@@ -16,7 +17,9 @@ pub struct DmenuItem {
 }
 
 impl DmenuItem {
-    /// Get a field value by dot-separated path (e.g. "title", "data.pid")
+    /// Get a field value by dot-separated path (e.g. "title", "data.pid").
+    /// Numeric segments index into arrays ("data.items.0.name"); a missing key or an
+    /// out-of-bounds index yields an empty string.
     pub fn get_field(&self, field: &str) -> String {
         match field {
             "title" => self.title.clone(),
@@ -32,7 +35,11 @@ impl DmenuItem {
                 } else if let Some(rest) = field.strip_prefix("data.") {
                     let mut current = data;
                     for key in rest.split('.') {
-                        match current.get(key) {
+                        let next = match (current, key.parse::<usize>()) {
+                            (serde_json::Value::Array(_), Ok(index)) => current.get(index),
+                            _ => current.get(key),
+                        };
+                        match next {
                             Some(v) => current = v,
                             None => return String::new(),
                         }
@@ -45,6 +52,29 @@ impl DmenuItem {
             _ => self.title.clone(),
         }
     }
+
+    /// Expand `{field}` placeholders (e.g. "{data.name} — {subtitle}") via `get_field`.
+    /// An unclosed `{` is kept literally.
+    pub fn render(&self, template: &str) -> String {
+        let mut result = String::with_capacity(template.len());
+        let mut rest = template;
+        while let Some(start) = rest.find('{') {
+            result.push_str(&rest[..start]);
+            match rest[start + 1..].find('}') {
+                Some(len) => {
+                    let field = &rest[start + 1..start + 1 + len];
+                    result.push_str(&self.get_field(field.trim()));
+                    rest = &rest[start + len + 2..];
+                }
+                None => {
+                    rest = &rest[start..];
+                    break;
+                }
+            }
+        }
+        result.push_str(rest);
+        result
+    }
 }
 
 /// Convert a JSON value to a plain string for action arguments
@@ -74,7 +104,8 @@ pub enum IconData {
 /// An item displayed in the fuzzy finder (daemon internal UI type)
 #[derive(Debug, Clone)]
 pub struct SourceItem {
-    /// Unique identifier within a session (e.g. original line index for dmenu items)
+    /// Unique identifier within a session: the original line index for dmenu items,
+    /// a daemon-wide counter for provider items (used to resolve the action)
     pub id: Option<usize>,
     /// Display title (e.g. app name)
     pub title: String,
@@ -87,3 +118,53 @@ pub struct SourceItem {
     /// Optional icon for display
     pub icon: Option<IconData>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(data: serde_json::Value) -> DmenuItem {
+        DmenuItem {
+            title: "Title".to_string(),
+            subtitle: None,
+            icon_path: None,
+            data: Some(data),
+        }
+    }
+
+    #[test]
+    fn get_field_indexes_into_arrays() {
+        let item = item(serde_json::json!({
+            "items": [{ "name": "first" }, { "name": "second", "tags": ["a", "b"] }],
+        }));
+        assert_eq!(item.get_field("data.items.0.name"), "first");
+        assert_eq!(item.get_field("data.items.1.name"), "second");
+        assert_eq!(item.get_field("data.items.1.tags.1"), "b");
+    }
+
+    #[test]
+    fn get_field_indexes_top_level_array() {
+        let item = item(serde_json::json!([10, 20, 30]));
+        assert_eq!(item.get_field("data.2"), "30");
+    }
+
+    #[test]
+    fn get_field_out_of_bounds_index_is_empty() {
+        let item = item(serde_json::json!({ "items": [{ "name": "first" }] }));
+        assert_eq!(item.get_field("data.items.1.name"), "");
+        assert_eq!(item.get_field("data.items.99"), "");
+    }
+
+    #[test]
+    fn get_field_numeric_object_keys_still_work() {
+        let item = item(serde_json::json!({ "0": "zero", "items": { "1": "one" } }));
+        assert_eq!(item.get_field("data.0"), "zero");
+        assert_eq!(item.get_field("data.items.1"), "one");
+    }
+
+    #[test]
+    fn get_field_non_numeric_segment_on_array_is_empty() {
+        let item = item(serde_json::json!({ "items": ["a"] }));
+        assert_eq!(item.get_field("data.items.name"), "");
+    }
+}
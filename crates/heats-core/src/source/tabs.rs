@@ -0,0 +1,135 @@
+use std::process::Command;
+
+/// A browser whose tabs can be listed and focused through AppleScript
+pub struct Browser {
+    /// Application name as AppleScript knows it
+    pub name: &'static str,
+    /// Where the app usually lives, for the row icon
+    pub bundle_path: &'static str,
+    /// AppleScript property holding a tab's title ("name" in Safari, "title" in Chrome)
+    title_property: &'static str,
+    /// Statement making tab `t` of window `w` the visible one
+    select_tab: &'static str,
+}
+
+/// Supported browsers, in listing order
+pub const BROWSERS: &[Browser] = &[
+    Browser {
+        name: "Safari",
+        bundle_path: "/Applications/Safari.app",
+        title_property: "name",
+        select_tab: "set current tab of window w to tab t of window w",
+    },
+    Browser {
+        name: "Google Chrome",
+        bundle_path: "/Applications/Google Chrome.app",
+        title_property: "title",
+        select_tab: "set active tab index of window w to t",
+    },
+];
+
+/// An open browser tab (window and tab are AppleScript's 1-based indices)
+pub struct TabEntry {
+    pub browser: &'static Browser,
+    pub window: usize,
+    pub tab: usize,
+    pub title: String,
+    pub url: String,
+}
+
+/// Tabs of every supported browser that is running. A browser that isn't running
+/// (or isn't installed, or denies Automation access) contributes no tabs.
+pub fn scan_tabs() -> Vec<TabEntry> {
+    BROWSERS.iter().flat_map(scan_browser).collect()
+}
+
+fn scan_browser(browser: &'static Browser) -> Vec<TabEntry> {
+    // The `is running` check keeps osascript from launching the browser.
+    // One line per tab: window, tab, URL and title (last, as it may contain tabs).
+    // `tab` names the browser's tab class inside the tell block, hence `sep`.
+    let script = format!(
+        r#"if application "{name}" is not running then return ""
+set sep to character id 9
+tell application "{name}"
+    set out to ""
+    repeat with w from 1 to count of windows
+        repeat with t from 1 to count of tabs of window w
+            set theTab to tab t of window w
+            set out to out & w & sep & t & sep & (URL of theTab) & sep & ({title} of theTab) & linefeed
+        end repeat
+    end repeat
+    return out
+end tell"#,
+        name = browser.name,
+        title = browser.title_property,
+    );
+    let output = match Command::new("osascript").arg("-e").arg(&script).output() {
+        Ok(output) if output.status.success() => output,
+        Ok(output) => {
+            tracing::warn!(
+                "Listing {} tabs failed: {}",
+                browser.name,
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+            return Vec::new();
+        }
+        Err(e) => {
+            tracing::warn!("Failed to run osascript: {}", e);
+            return Vec::new();
+        }
+    };
+
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.splitn(4, '\t');
+            let window = fields.next()?.parse().ok()?;
+            let tab = fields.next()?.parse().ok()?;
+            let url = fields.next()?.to_string();
+            let title = fields.next().unwrap_or_default().to_string();
+            Some(TabEntry {
+                browser,
+                window,
+                tab,
+                title,
+                url,
+            })
+        })
+        .collect()
+}
+
+/// Bring tab `tab` of window `window` of `browser_name` to the front.
+/// Returns false if the browser is unknown or the script failed.
+pub fn focus_tab(browser_name: &str, window: usize, tab: usize) -> bool {
+    let Some(browser) = BROWSERS.iter().find(|b| b.name == browser_name) else {
+        tracing::warn!("focus_tab: unsupported browser '{}'", browser_name);
+        return false;
+    };
+    // Select the tab before raising its window: raising renumbers the windows
+    let script = format!(
+        r#"on run argv
+    set w to (item 1 of argv) as integer
+    set t to (item 2 of argv) as integer
+    tell application "{name}"
+        {select_tab}
+        set index of window w to 1
+        activate
+    end tell
+end run"#,
+        name = browser.name,
+        select_tab = browser.select_tab,
+    );
+    match Command::new("osascript")
+        .arg("-e")
+        .arg(&script)
+        .arg(window.to_string())
+        .arg(tab.to_string())
+        .status()
+    {
+        Ok(status) => status.success(),
+        Err(e) => {
+            tracing::warn!("Failed to run osascript: {}", e);
+            false
+        }
+    }
+}
@@ -1,9 +1,11 @@
+use core_foundation::array::CFArray;
 use core_foundation::base::TCFType;
 use core_foundation::number::{CFNumber, CFNumberRef};
 use core_foundation::string::{CFString, CFStringRef};
 
 use crate::platform;
 
+use std::collections::HashMap;
 use std::ffi::c_void;
 
 extern "C" {
@@ -12,10 +14,18 @@ extern "C" {
     fn CFArrayGetValueAtIndex(array: *const c_void, idx: isize) -> *const c_void;
     fn CFDictionaryGetValue(dict: *const c_void, key: *const c_void) -> *const c_void;
     fn CFRelease(cf: *const c_void);
+    // Private SkyLight (CoreGraphics Services) calls for Spaces; there is no public API
+    fn CGSMainConnectionID() -> i32;
+    fn CGSCopySpacesForWindows(cid: i32, mask: i32, window_ids: *const c_void) -> *const c_void;
+    fn CGSCopyManagedDisplaySpaces(cid: i32) -> *const c_void;
 }
 
 const K_CG_WINDOW_LIST_ON_SCREEN_ONLY: u32 = 1 << 0;
 const K_CG_WINDOW_LIST_EXCLUDE_DESKTOP: u32 = 1 << 4;
+/// kCGSAllSpacesMask: current, other and fullscreen Spaces
+const K_CGS_ALL_SPACES_MASK: i32 = 0x7;
+/// Space `type` of a regular desktop (fullscreen app Spaces have other types)
+const SPACE_TYPE_DESKTOP: i64 = 0;
 
 /// A raw window entry (no icon loading)
 pub struct WindowEntry {
@@ -24,6 +34,9 @@ pub struct WindowEntry {
     pub pid: i64,
     pub wid: i64,
     pub bundle_path: Option<String>,
+    /// Mission Control desktop number ("Desktop N") of the window's Space, per display.
+    /// None when it can't be determined or the window is on a fullscreen Space.
+    pub space: Option<usize>,
 }
 
 /// Scan on-screen windows via CGWindowListCopyWindowInfo.
@@ -31,6 +44,7 @@ pub struct WindowEntry {
 pub fn scan_windows_raw() -> Vec<WindowEntry> {
     let mut entries = Vec::new();
     let self_pid = std::process::id() as i64;
+    let space_numbers = desktop_space_numbers();
 
     unsafe {
         let options = K_CG_WINDOW_LIST_ON_SCREEN_ONLY | K_CG_WINDOW_LIST_EXCLUDE_DESKTOP;
@@ -75,12 +89,15 @@ pub fn scan_windows_raw() -> Vec<WindowEntry> {
 
             let bundle_path = platform::macos::bundle_path_for_pid(pid as i32);
 
+            let space = window_space(wid).and_then(|id| space_numbers.get(&id).copied());
+
             entries.push(WindowEntry {
                 owner,
                 title,
                 pid,
                 wid,
                 bundle_path,
+                space,
             });
         }
 
@@ -91,6 +108,72 @@ pub fn scan_windows_raw() -> Vec<WindowEntry> {
     entries
 }
 
+/// Map each desktop Space id to its 1-based number on its display, in Mission Control
+/// order. Empty if the Space layout can't be read.
+fn desktop_space_numbers() -> HashMap<i64, usize> {
+    let key_spaces = CFString::new("Spaces");
+    let key_space_id = CFString::new("ManagedSpaceID");
+    let key_type = CFString::new("type");
+
+    let mut numbers = HashMap::new();
+    unsafe {
+        let displays = CGSCopyManagedDisplaySpaces(CGSMainConnectionID());
+        if displays.is_null() {
+            return numbers;
+        }
+        for i in 0..CFArrayGetCount(displays) {
+            let display = CFArrayGetValueAtIndex(displays, i);
+            if display.is_null() {
+                continue;
+            }
+            let spaces =
+                CFDictionaryGetValue(display, key_spaces.as_concrete_TypeRef() as *const c_void);
+            if spaces.is_null() {
+                continue;
+            }
+            let mut number = 0;
+            for j in 0..CFArrayGetCount(spaces) {
+                let space = CFArrayGetValueAtIndex(spaces, j);
+                let is_desktop = !space.is_null()
+                    && dict_get_number(space, &key_type) == Some(SPACE_TYPE_DESKTOP);
+                if !is_desktop {
+                    continue;
+                }
+                number += 1;
+                if let Some(id) = dict_get_number(space, &key_space_id) {
+                    numbers.insert(id, number);
+                }
+            }
+        }
+        CFRelease(displays);
+    }
+    numbers
+}
+
+/// Id of the Space a window is on (the first one, for windows shown on all Spaces).
+fn window_space(wid: i64) -> Option<i64> {
+    let window_ids = CFArray::from_CFTypes(&[CFNumber::from(wid)]);
+    unsafe {
+        let spaces = CGSCopySpacesForWindows(
+            CGSMainConnectionID(),
+            K_CGS_ALL_SPACES_MASK,
+            window_ids.as_concrete_TypeRef() as *const c_void,
+        );
+        if spaces.is_null() {
+            return None;
+        }
+        let space = match CFArrayGetCount(spaces) {
+            0 => None,
+            _ => {
+                let value = CFArrayGetValueAtIndex(spaces, 0);
+                CFNumber::wrap_under_get_rule(value as CFNumberRef).to_i64()
+            }
+        };
+        CFRelease(spaces);
+        space
+    }
+}
+
 unsafe fn dict_get_number(dict: *const c_void, key: &CFString) -> Option<i64> {
     let val = CFDictionaryGetValue(dict, key.as_concrete_TypeRef() as *const c_void);
     if val.is_null() {
@@ -1,8 +1,8 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
 
-use iced::widget::{column, container};
+use iced::widget::{column, container, row, space, text};
 use iced::window;
 use iced::{event, keyboard, Color, Element, Fill, Padding, Point, Size, Subscription, Task, Theme};
 use tokio::sync::oneshot;
@@ -12,20 +12,27 @@ use crate::evaluator;
 use crate::hotkey::{self, HotkeyMessage};
 use crate::ipc_server;
 use crate::matcher::engine::Matcher;
-use crate::ui::{result_list, search_input, tab_bar, theme};
-use heats_core::config::{Config, WindowMode};
-use heats_core::source::SourceItem;
+use crate::signal;
+use crate::ui::{hint_bar, preview, result_list, search_input, tab_bar, theme};
+use heats_core::config::{Config, Trigger, WindowMode};
+use heats_core::ipc::IpcContext;
+use heats_core::source::{IconData, SourceItem};
 
 pub struct State {
     config: Config,
     matcher: Matcher,
-    all_items: Vec<SourceItem>,
-    results: Vec<SourceItem>,
+    all_items: Vec<Arc<SourceItem>>,
+    results: Vec<Arc<SourceItem>>,
     query: String,
     selected: usize,
 
     /// Current window ID
     window_id: Option<window::Id>,
+    /// Normal mode: window requested by the latest show whose `WindowOpened` hasn't
+    /// arrived yet. A hide clears it, so the late window gets closed instead of adopted.
+    opening_window: Option<window::Id>,
+    /// When the hotkey last toggled the launcher, to drop presses that bounce right back
+    last_toggle: Option<Instant>,
     /// Whether the launcher is currently shown
     visible: bool,
     /// Fixed display bounds (only used in Fixed mode)
@@ -38,9 +45,13 @@ pub struct State {
     loaded_items: Vec<LoadedItem>,
 
     /// Active dmenu session response channel (returns selected item's ID)
-    dmenu_tx: Option<oneshot::Sender<Option<usize>>>,
+    dmenu_tx: Option<oneshot::Sender<Option<DmenuSelection>>>,
     /// Whether current session is dmenu (external items) vs built-in
     is_dmenu_session: bool,
+    /// Options the client sent for the active dmenu session
+    dmenu_context: IpcContext,
+    /// Item IDs marked with Tab in a `multi` dmenu session, in marking order
+    dmenu_marked: Vec<usize>,
 
     /// Background cache: provider name → cached items
     provider_cache: HashMap<String, Vec<LoadedItem>>,
@@ -55,11 +66,70 @@ pub struct State {
     active_evaluators: Vec<String>,
     /// Current mode index into config.mode (None when dmenu session)
     current_mode_index: Option<usize>,
+    /// `trigger = "on_query"` providers of the current mode not loaded yet
+    deferred_providers: Vec<String>,
+    /// Whether a manual (Cmd+R) refresh is waiting for provider results
+    refreshing: bool,
+    /// Why config.toml failed to load (defaults are in use); shown until the first hide
+    config_error: Option<String>,
+    /// Back-stack of (mode name, query) recorded on hide, restored with Cmd+[
+    nav_history: Vec<(String, String)>,
+    /// Time of the last keyboard input (or show), for the idle timeout
+    last_input: Instant,
+    /// Current window height (differs from `config.window.height` with `auto_height`)
+    window_height: f32,
+    /// IDs of the current mode's pinned items in `results`
+    pinned_ids: HashSet<usize>,
+    /// ID of the result the user navigated to; `selected` follows it when results reorder.
+    /// None until the user moves the selection (and again after the query changes).
+    selected_id: Option<usize>,
+    /// Whether the window gained focus since it was last shown; a focus loss only
+    /// hides it (`hide_on_blur`) after that, so a show that never took focus stays up
+    focused_since_show: bool,
+    /// Output of the selected item's provider `preview` command, by item ID
+    preview_output: Option<(usize, String)>,
+    /// Item ID of the preview command in flight. Replacing the handle aborts the
+    /// previous run (and kills its process), so a fast-moving selection never piles up.
+    preview_pending: Option<(usize, iced::task::Handle)>,
+    /// Index of the result row under the mouse cursor; cleared when the keyboard
+    /// moves the selection so only one row stands out
+    hovered: Option<usize>,
+    /// Modifier keys currently held (Alt+Enter selects with the alternate action)
+    modifiers: keyboard::Modifiers,
+    /// Enter was pressed while matching was still running; the MatcherTick that
+    /// sees it settle executes the selection
+    execute_pending: bool,
+    /// Pasteboard change count when the clipboard history last looked at it
+    clipboard_change_count: Option<isize>,
 }
 
+/// Maximum number of entries kept in the Cmd+[ navigation history
+const NAV_HISTORY_LIMIT: usize = 20;
+
+/// Rows shown for a new dmenu session before matching has caught up
+const DMENU_INITIAL_ROWS: usize = 50;
+
+/// How long the selection has to rest on an item before its preview command runs
+const PREVIEW_DEBOUNCE: Duration = Duration::from_millis(100);
+
+/// Hotkey presses this soon after the previous toggle are ignored, so mashing the
+/// hotkey doesn't open and close windows faster than they can settle
+const TOGGLE_DEBOUNCE: Duration = Duration::from_millis(150);
+
+/// How often the pasteboard is checked for new text (`[clipboard] history`)
+const CLIPBOARD_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
 /// Wrapper to make oneshot::Sender cloneable for Message (taken once via take()).
 #[derive(Clone)]
-pub struct ResponseSender(pub Arc<Mutex<Option<oneshot::Sender<Option<usize>>>>>);
+pub struct ResponseSender(pub Arc<Mutex<Option<oneshot::Sender<Option<DmenuSelection>>>>>);
+
+/// What a dmenu session answers with: the chosen item IDs and how they were chosen
+#[derive(Debug)]
+pub struct DmenuSelection {
+    pub ids: Vec<usize>,
+    /// `heats_core::ipc::ACTION_DEFAULT` or `ACTION_ALT`
+    pub action: &'static str,
+}
 
 impl std::fmt::Debug for ResponseSender {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -70,44 +140,78 @@ impl std::fmt::Debug for ResponseSender {
 #[derive(Debug, Clone)]
 pub enum Message {
     WindowOpened(window::Id),
+    /// The Fixed mode window was created at boot (hidden)
+    WindowCreated,
     WindowClosed(window::Id),
     QueryChanged(String),
     Execute,
     SelectAndExecute(usize),
+    /// The mouse entered / left the result row at this index
+    Hover(usize),
+    Unhover(usize),
     ItemsLoaded(Vec<LoadedItem>),
     MatcherTick,
     KeyEvent(keyboard::Event),
     Hotkey(HotkeyMessage),
+    /// Show a mode requested over IPC (`{"op":"show"}`)
+    ShowMode(String),
+    /// Hide requested over IPC (`{"op":"hide"}`)
+    Hide,
+    /// SIGINT/SIGTERM received: hide, close the window and exit the daemon
+    Shutdown,
+    /// The launcher window gained (true) or lost (false) focus
+    WindowFocus(bool),
     ActivateWindow,
     DmenuSession {
         items: Vec<SourceItem>,
+        context: IpcContext,
         response_tx: ResponseSender,
     },
+    /// More items for the open `live` dmenu session
+    DmenuAppend(Vec<SourceItem>),
+    /// A dmenu session's `timeout_ms` elapsed; the client has been cancelled
+    DmenuTimedOut,
     /// Timer tick for background cache refresh
     CacheRefresh,
+    /// Timer tick for checking the pasteboard (`[clipboard] history`)
+    ClipboardPoll,
     /// Background cache updated for a provider
     CacheUpdated {
         provider_name: String,
         items: Vec<LoadedItem>,
     },
+    /// Cached items restored from disk at startup
+    CacheRestored {
+        provider_name: String,
+        items: Vec<LoadedItem>,
+    },
     /// Evaluator results (debounced)
     EvalResults {
         generation: u64,
         items: Vec<LoadedItem>,
     },
+    /// Periodic idle check while visible; hides once `idle_timeout_secs` has elapsed
+    IdleTimeout,
+    /// An item's icon finished loading in the background
+    IconLoaded {
+        id: usize,
+        icon: IconData,
+    },
+    /// A provider `preview` command finished for the item with this ID
+    PreviewLoaded {
+        id: usize,
+        output: String,
+    },
 }
 
 impl State {
     pub fn new(
         config: Config,
+        config_error: Option<String>,
         manager: global_hotkey::GlobalHotKeyManager,
         hotkey_modes: Vec<(u32, String)>,
     ) -> (Self, Task<Message>) {
-        let fixed_display = if config.window.display.is_empty() {
-            heats_core::platform::macos::focused_display_bounds()
-        } else {
-            heats_core::platform::macos::display_bounds_by_name(&config.window.display)
-        };
+        let fixed_display = Self::resolve_fixed_display(&config.window.display);
 
         tracing::info!(
             "Window mode: {:?}, display bounds: {:?}",
@@ -122,6 +226,7 @@ impl State {
                 &fixed_display,
                 config.window.width,
                 config.window.height,
+                config.window.y_ratio(),
             );
             let (id, open_task) = window::open(window::Settings {
                 size: Size::new(config.window.width, config.window.height),
@@ -134,19 +239,23 @@ impl State {
                 exit_on_close_request: false,
                 ..window::Settings::default()
             });
-            (Some(id), open_task.discard())
+            (Some(id), open_task.map(|_| Message::WindowCreated))
         } else {
             (None, Task::none())
         };
 
-        let state = Self {
+        let matcher = Matcher::new(&config.matcher);
+        let window_height = config.window.height;
+        let mut state = Self {
             config,
-            matcher: Matcher::new(),
+            matcher,
             all_items: Vec::new(),
             results: Vec::new(),
             query: String::new(),
             selected: 0,
             window_id,
+            opening_window: None,
+            last_toggle: None,
             visible: false,
             fixed_display,
             _hotkey_manager: manager,
@@ -154,18 +263,39 @@ impl State {
             loaded_items: Vec::new(),
             dmenu_tx: None,
             is_dmenu_session: false,
+            dmenu_context: IpcContext::default(),
+            dmenu_marked: Vec::new(),
             provider_cache: HashMap::new(),
             cache_last_updated: HashMap::new(),
             eval_items: Vec::new(),
             eval_generation: 0,
             active_evaluators: Vec::new(),
             current_mode_index: None,
+            deferred_providers: Vec::new(),
+            refreshing: false,
+            config_error,
+            nav_history: Vec::new(),
+            last_input: Instant::now(),
+            window_height,
+            pinned_ids: HashSet::new(),
+            selected_id: None,
+            focused_since_show: false,
+            preview_output: None,
+            preview_pending: None,
+            hovered: None,
+            modifiers: keyboard::Modifiers::default(),
+            execute_pending: false,
+            clipboard_change_count: None,
         };
 
-        // Kick initial cache load for providers with cache_interval
-        let initial_cache_task = state.initial_cache_load();
+        // Restore disk caches, then load only the providers whose cache is missing or stale
+        let restore_task = state.restore_disk_caches();
+        let initial_cache_task = state.refresh_stale_caches();
 
-        (state, Task::batch([boot_task, initial_cache_task]))
+        (
+            state,
+            Task::batch([boot_task, restore_task, initial_cache_task]),
+        )
     }
 
     pub fn title(&self, _window: window::Id) -> String {
@@ -174,9 +304,20 @@ impl State {
 
     pub fn update(&mut self, message: Message) -> Task<Message> {
         match message {
+            Message::WindowCreated => {
+                self.apply_window_blur();
+                Task::none()
+            }
             Message::WindowOpened(id) => {
                 tracing::debug!("WindowOpened: id={:?}, is_dmenu={}", id, self.is_dmenu_session);
+                // A hide (or a newer show) came in while this window was opening
+                if self.opening_window != Some(id) {
+                    tracing::debug!("WindowOpened: window {:?} no longer wanted, closing", id);
+                    return window::close(id);
+                }
+                self.opening_window = None;
                 self.window_id = Some(id);
+                self.apply_window_blur();
                 // Delay native focus to next run loop iteration so macOS has
                 // time to fully realize the window before we activate it
                 let activate = Task::perform(
@@ -187,6 +328,9 @@ impl State {
                     window::gain_focus(id),
                     iced::widget::operation::focus(search_input::SEARCH_INPUT_ID),
                     activate,
+                    // Results (e.g. from the cache) may have arrived before the window
+                    // existed, when `auto_height` couldn't resize it yet
+                    self.fit_window_height(),
                 ])
             }
             Message::WindowClosed(id) => {
@@ -198,6 +342,9 @@ impl State {
                 );
                 if self.window_id == Some(id) {
                     self.window_id = None;
+                    if self.visible {
+                        self.run_hook(self.config.on_hide.as_deref());
+                    }
                     self.visible = false;
                     self.cancel_dmenu_session();
                     self.reset_state();
@@ -205,85 +352,74 @@ impl State {
                 Task::none()
             }
             Message::QueryChanged(query) => {
+                self.last_input = Instant::now();
                 self.query = query.clone();
                 self.selected = 0;
+                self.selected_id = None;
+                self.execute_pending = false;
                 self.matcher.update_query(&query);
 
+                let deferred_task = self.load_deferred_providers(&query);
+
                 // Trigger evaluators with debounce
                 tracing::debug!(
                     "QueryChanged: active_evaluators={:?}, query='{}'",
                     self.active_evaluators, query
                 );
-                if !self.active_evaluators.is_empty() && !query.is_empty() {
+                let evaluator_names = evaluator::eligible_evaluators(
+                    &query,
+                    &self.active_evaluators,
+                    &self.config.evaluator,
+                );
+                let eval_task = if !evaluator_names.is_empty() {
                     self.eval_generation += 1;
                     let gen = self.eval_generation;
-                    let evaluator_names = self.active_evaluators.clone();
+                    let delay = evaluator::debounce(&evaluator_names, &self.config.evaluator);
                     let configs = self.config.evaluator.clone();
                     Task::perform(
                         async move {
-                            tokio::time::sleep(Duration::from_millis(100)).await;
+                            tokio::time::sleep(delay).await;
                             let items = evaluator::run_evaluators(&query, &evaluator_names, &configs).await;
                             (gen, items)
                         },
                         |(generation, items)| Message::EvalResults { generation, items },
                     )
                 } else {
+                    // Invalidate in-flight results from a longer query
+                    self.eval_generation += 1;
                     self.eval_items.clear();
                     Task::none()
-                }
+                };
+                Task::batch([deferred_task, eval_task])
             }
             Message::Execute => {
-                let eval_count = self.eval_items.len();
-                if self.selected < eval_count {
-                    // Selected an evaluator result
-                    let eval_action = self.pending_eval_action(self.selected);
-                    let hide_task = self.hide();
-                    if let Some((config, dmenu_item)) = eval_action {
-                        command::run_action(&config, &dmenu_item);
-                    }
-                    return hide_task;
-                }
-
-                let adjusted = self.selected - eval_count;
-                if let Some(item) = self.results.get(adjusted) {
-                    if self.is_dmenu_session {
-                        self.send_dmenu_response(item.id);
-                    }
+                // Enter right after typing: act on results for the current query,
+                // not on whatever the last timer tick left behind. If matching is still
+                // running, the MatcherTick that sees it settle runs the action.
+                if self.matcher.needs_tick() {
+                    self.refresh_results();
                 }
-                // Capture action info before hide() clears state
-                let action = self.pending_action(adjusted);
-                // Hide first so macOS deactivates Heats before the action
-                // activates the target app — avoids focus bounce-back
-                let hide_task = self.hide();
-                if let Some((provider, dmenu_item)) = action {
-                    command::execute_action(&provider, &dmenu_item);
+                if self.matcher.needs_tick() {
+                    tracing::debug!("Execute deferred until matching settles");
+                    self.execute_pending = true;
+                    return Task::none();
                 }
-                hide_task
+                self.execute_selected()
             }
             Message::SelectAndExecute(index) => {
                 self.selected = index;
-                let eval_count = self.eval_items.len();
-                if index < eval_count {
-                    let eval_action = self.pending_eval_action(index);
-                    let hide_task = self.hide();
-                    if let Some((config, dmenu_item)) = eval_action {
-                        command::run_action(&config, &dmenu_item);
-                    }
-                    return hide_task;
-                }
-
-                let adjusted = index - eval_count;
-                if let Some(item) = self.results.get(adjusted) {
-                    if self.is_dmenu_session {
-                        self.send_dmenu_response(item.id);
-                    }
-                }
-                let action = self.pending_action(adjusted);
-                let hide_task = self.hide();
-                if let Some((provider, dmenu_item)) = action {
-                    command::execute_action(&provider, &dmenu_item);
+                self.execute_selected()
+            }
+            Message::Hover(index) => {
+                self.hovered = Some(index);
+                Task::none()
+            }
+            Message::Unhover(index) => {
+                // Entering the next row may be handled before leaving this one
+                if self.hovered == Some(index) {
+                    self.hovered = None;
                 }
-                hide_task
+                Task::none()
             }
             Message::ItemsLoaded(loaded_items) => {
                 // Ignore items while a dmenu session is active
@@ -291,63 +427,39 @@ impl State {
                     tracing::debug!("ItemsLoaded ignored (dmenu session active)");
                     return Task::none();
                 }
+                self.refreshing = false;
+                let icon_task = load_icons(&loaded_items);
+                let cache_task = self.cache_loaded_items(&loaded_items);
                 // Merge with existing items (cache may have pre-populated some)
                 if self.loaded_items.is_empty() {
                     self.loaded_items = loaded_items;
                 } else {
                     self.loaded_items.extend(loaded_items);
+                    self.sort_by_provider_order();
                 }
-                self.all_items = self.loaded_items.iter().map(|li| li.item.clone()).collect();
+                self.all_items = self
+                    .loaded_items
+                    .iter()
+                    .map(|li| Arc::new(li.item.clone()))
+                    .collect();
                 self.matcher.set_items(self.all_items.clone());
                 self.results = self.all_items.clone();
+                self.arrange_results();
                 // No focus call here — WindowOpened already handled focus
-                Task::none()
+                Task::batch([icon_task, cache_task])
             }
             Message::MatcherTick => {
-                let changed = self.matcher.tick();
-                if changed {
-                    self.results = if self.matcher.query_is_empty() {
-                        self.all_items.clone()
-                    } else {
-                        self.matcher.results(50)
-                    };
+                self.refresh_results();
+                if self.execute_pending && !self.matcher.needs_tick() {
+                    self.execute_pending = false;
+                    return self.execute_selected();
                 }
-                Task::none()
+                self.results_settled()
+            }
+            Message::KeyEvent(kb_event) => {
+                self.last_input = Instant::now();
+                self.handle_key(kb_event)
             }
-            Message::KeyEvent(kb_event) => match kb_event {
-                keyboard::Event::KeyPressed {
-                    key: keyboard::Key::Named(keyboard::key::Named::Escape),
-                    ..
-                } => self.hide(),
-                keyboard::Event::KeyPressed {
-                    key: keyboard::Key::Named(keyboard::key::Named::Tab),
-                    modifiers,
-                    ..
-                } if modifiers.control() => {
-                    let offset = if modifiers.shift() { -1 } else { 1 };
-                    self.switch_mode_by_offset(offset)
-                }
-                keyboard::Event::KeyPressed {
-                    key: keyboard::Key::Named(keyboard::key::Named::ArrowUp),
-                    ..
-                } => {
-                    if self.selected > 0 {
-                        self.selected -= 1;
-                    }
-                    Task::none()
-                }
-                keyboard::Event::KeyPressed {
-                    key: keyboard::Key::Named(keyboard::key::Named::ArrowDown),
-                    ..
-                } => {
-                    let total = self.eval_items.len() + self.results.len();
-                    if self.selected + 1 < total {
-                        self.selected += 1;
-                    }
-                    Task::none()
-                }
-                _ => Task::none(),
-            },
             Message::ActivateWindow => {
                 heats_core::platform::macos::native_focus_heats_window();
                 if let Some(id) = self.window_id {
@@ -360,19 +472,71 @@ impl State {
                 }
             }
             Message::Hotkey(hotkey_msg) => {
-                let mode_name = hotkey_msg.mode_name;
+                if self
+                    .last_toggle
+                    .is_some_and(|at| at.elapsed() < TOGGLE_DEBOUNCE)
+                {
+                    tracing::debug!("Hotkey ignored (pressed again too soon)");
+                    return Task::none();
+                }
+                self.last_toggle = Some(Instant::now());
+                self.toggle(&hotkey_msg.mode_name)
+            }
+            Message::ShowMode(mode_name) => {
+                if !self.visible {
+                    return self.toggle(&mode_name);
+                }
+                // Never interrupt a script's picker; otherwise switch to the requested mode
+                if self.is_dmenu_session {
+                    tracing::debug!("ShowMode ignored (dmenu session active)");
+                    return Task::none();
+                }
+                match self.config.mode.iter().position(|m| m.name == mode_name) {
+                    Some(index) if Some(index) != self.current_mode_index => self.load_mode(index),
+                    Some(_) => Task::none(),
+                    None => {
+                        tracing::warn!("ShowMode: unknown mode '{}'", mode_name);
+                        Task::none()
+                    }
+                }
+            }
+            Message::Hide => {
                 if self.visible {
                     self.hide()
                 } else {
-                    // If a dmenu session is active, cancel it before showing
-                    if self.is_dmenu_session {
-                        self.cancel_dmenu_session();
-                        self.reset_state();
-                    }
-                    self.show_mode(&mode_name)
+                    Task::none()
                 }
             }
-            Message::DmenuSession { items, response_tx } => {
+            Message::WindowFocus(true) => {
+                self.focused_since_show = true;
+                Task::none()
+            }
+            Message::WindowFocus(false) => {
+                if self.visible && self.focused_since_show && self.config.window.hide_on_blur {
+                    tracing::debug!("Window lost focus, hiding");
+                    self.hide()
+                } else {
+                    Task::none()
+                }
+            }
+            Message::Shutdown => {
+                tracing::info!("Shutting down");
+                let hide_task = if self.visible {
+                    self.hide()
+                } else {
+                    Task::none()
+                };
+                let close_task = match self.window_id.take() {
+                    Some(id) => window::close(id),
+                    None => Task::none(),
+                };
+                hide_task.chain(close_task).chain(iced::exit())
+            }
+            Message::DmenuSession {
+                items,
+                context,
+                response_tx,
+            } => {
                 tracing::debug!(
                     "DmenuSession: {} items, visible={}, window_id={:?}",
                     items.len(),
@@ -386,15 +550,36 @@ impl State {
                     // hide() already cancelled any active dmenu + reset state
 
                     let tx = response_tx.0.lock().unwrap().take();
-                    self.start_dmenu_session(items, tx);
+                    self.start_dmenu_session(items, context, tx);
                     let show_task = self.show_dmenu();
                     Task::batch([hide_task, show_task])
                 } else {
                     let tx = response_tx.0.lock().unwrap().take();
-                    self.start_dmenu_session(items, tx);
+                    self.start_dmenu_session(items, context, tx);
                     self.show_dmenu()
                 }
             }
+            Message::DmenuAppend(items) => {
+                if !self.is_dmenu_session {
+                    return Task::none();
+                }
+                tracing::debug!("DmenuAppend: {} items", items.len());
+                // The next MatcherTick picks the new items up (and refreshes `results`)
+                let items: Vec<Arc<SourceItem>> = items.into_iter().map(Arc::new).collect();
+                self.matcher.append_items_with_subtitle(items.clone());
+                self.all_items.extend(items);
+                Task::none()
+            }
+            Message::DmenuTimedOut => {
+                // Only hide if the timed-out session is still the one on screen
+                let timed_out = self.dmenu_tx.as_ref().is_some_and(|tx| tx.is_closed());
+                if self.is_dmenu_session && timed_out {
+                    tracing::debug!("Dmenu session timed out, hiding");
+                    self.hide()
+                } else {
+                    Task::none()
+                }
+            }
             Message::EvalResults { generation, items } => {
                 tracing::debug!(
                     "EvalResults: gen={}, current_gen={}, items={}",
@@ -402,54 +587,212 @@ impl State {
                 );
                 if generation == self.eval_generation {
                     self.eval_items = items;
-                    // Clamp selected index to valid range
-                    let total = self.eval_items.len() + self.results.len();
-                    if total > 0 && self.selected >= total {
-                        self.selected = total - 1;
-                    }
+                    self.sync_selection();
+                    return self.results_settled();
                 }
                 Task::none()
             }
             Message::CacheRefresh => {
                 self.refresh_stale_caches()
             }
+            Message::ClipboardPoll => self.record_clipboard(),
             Message::CacheUpdated { provider_name, items } => {
                 tracing::debug!(
                     "CacheUpdated: provider='{}', {} items",
                     provider_name,
                     items.len()
                 );
+                // Already written to the disk cache by the load task (off the UI thread)
+                let icon_task = load_icons(&items);
                 self.provider_cache.insert(provider_name.clone(), items);
                 self.cache_last_updated.insert(provider_name, Instant::now());
+                icon_task
+            }
+            Message::CacheRestored {
+                provider_name,
+                items,
+            } => {
+                // A fresh load may have finished first; never overwrite it with disk data
+                if !self.provider_cache.contains_key(&provider_name) {
+                    tracing::debug!(
+                        "CacheRestored: provider='{}', {} items",
+                        provider_name,
+                        items.len()
+                    );
+                    let icon_task = load_icons(&items);
+                    self.provider_cache.insert(provider_name, items);
+                    return icon_task;
+                }
+                Task::none()
+            }
+            Message::IdleTimeout => match self.idle_timeout() {
+                Some(timeout) if self.visible && self.last_input.elapsed() >= timeout => {
+                    tracing::debug!("Idle timeout elapsed, hiding launcher");
+                    self.hide()
+                }
+                _ => Task::none(),
+            },
+            Message::IconLoaded { id, icon } => {
+                self.set_icon(id, icon);
+                Task::none()
+            }
+            Message::PreviewLoaded { id, output } => {
+                if matches!(&self.preview_pending, Some((pending, _)) if *pending == id) {
+                    self.preview_pending = None;
+                    self.preview_output = Some((id, output));
+                }
                 Task::none()
             }
         }
     }
 
+    /// Handle a key press forwarded by the keyboard subscription.
+    fn handle_key(&mut self, kb_event: keyboard::Event) -> Task<Message> {
+        match kb_event {
+            keyboard::Event::ModifiersChanged(modifiers) => {
+                self.modifiers = modifiers;
+                Task::none()
+            }
+            keyboard::Event::KeyPressed {
+                key: keyboard::Key::Named(keyboard::key::Named::Escape),
+                ..
+            } => {
+                if self.config.window.escape_clears_query && !self.query.is_empty() {
+                    self.update(Message::QueryChanged(String::new()))
+                } else {
+                    self.hide()
+                }
+            }
+            keyboard::Event::KeyPressed {
+                key: keyboard::Key::Named(keyboard::key::Named::Tab),
+                modifiers,
+                ..
+            } if modifiers.control() => {
+                let offset = if modifiers.shift() { -1 } else { 1 };
+                self.switch_mode_by_offset(offset)
+            }
+            keyboard::Event::KeyPressed {
+                key: keyboard::Key::Named(keyboard::key::Named::Tab),
+                ..
+            } if self.is_dmenu_session && self.dmenu_context.multi => self.toggle_dmenu_mark(),
+            keyboard::Event::KeyPressed {
+                key: keyboard::Key::Named(keyboard::key::Named::Tab),
+                modifiers,
+                ..
+            } if !modifiers.shift() => self.complete_query(),
+            keyboard::Event::KeyPressed {
+                key: keyboard::Key::Character(c),
+                modifiers,
+                ..
+            } if modifiers.command() && c.as_str() == "[" => self.navigate_back(),
+            keyboard::Event::KeyPressed {
+                key: keyboard::Key::Character(c),
+                modifiers,
+                ..
+            } if modifiers.command() && c.as_str() == "r" => self.refresh_current_mode(),
+            keyboard::Event::KeyPressed {
+                key: keyboard::Key::Character(c),
+                modifiers,
+                ..
+            } if is_copy_item(&c, modifiers) => self.copy_selected(),
+            keyboard::Event::KeyPressed { key, modifiers, .. }
+                if modifiers.command() && digit_shortcut(&key).is_some() =>
+            {
+                let position = digit_shortcut(&key).unwrap_or(1) - 1;
+                self.execute_visible(position)
+            }
+            keyboard::Event::KeyPressed {
+                key: keyboard::Key::Named(keyboard::key::Named::ArrowUp),
+                ..
+            } => {
+                if self.selected > 0 {
+                    self.selected -= 1;
+                }
+                self.hovered = None;
+                self.remember_selection();
+                self.schedule_preview()
+            }
+            keyboard::Event::KeyPressed {
+                key: keyboard::Key::Named(keyboard::key::Named::ArrowDown),
+                ..
+            } => {
+                let total = self.eval_items.len() + self.results.len();
+                if self.selected + 1 < total {
+                    self.selected += 1;
+                }
+                self.hovered = None;
+                self.remember_selection();
+                self.schedule_preview()
+            }
+            _ => Task::none(),
+        }
+    }
+
     pub fn view(&self, _window: window::Id) -> Element<'_, Message> {
-        let input = search_input::view(&self.query);
+        let placeholder = self
+            .dmenu_context
+            .prompt
+            .as_deref()
+            .unwrap_or(search_input::DEFAULT_PLACEHOLDER);
+        let icon = match self.current_mode_index {
+            Some(index) => self.config.mode[index].icon.as_deref(),
+            None => self.dmenu_context.icon.as_deref(),
+        };
+        let icon = icon.and_then(|icon| icon.chars().next());
+        let input = search_input::view(&self.query, placeholder, icon, self.dmenu_context.password);
+
+        // "3 / 128": selected position and total result count, hidden when empty
+        let total = self.eval_items.len() + self.results.len();
+        let input: Element<'_, Message> = if total > 0 {
+            let position = text(format!("{} / {}", self.selected + 1, total))
+                .size(12)
+                .color(theme::TEXT_SECONDARY);
+            row![input, position]
+                .spacing(8)
+                .align_y(iced::Alignment::Center)
+                .into()
+        } else {
+            input
+        };
 
-        // Merge evaluator results (at top) with provider results
-        let display_items: Vec<&SourceItem> = self
-            .eval_items
-            .iter()
-            .map(|li| &li.item)
-            .chain(self.results.iter())
-            .collect();
+        let display_items = self.display_items();
+        let results = result_list::view(&display_items, self.selected, &self.list_options());
+        let results: Element<'_, Message> = match self.preview_content() {
+            Some(content) => row![results, preview::view(content)]
+                .spacing(8)
+                .height(Fill)
+                .into(),
+            None => results,
+        };
 
-        let show_tabs = !self.is_dmenu_session && self.config.mode.len() > 1;
-        let results = result_list::view(&display_items, self.selected, self.config.window.height, show_tabs);
+        let show_tabs = self.show_tabs();
 
         let mut content = column![].spacing(8).padding(Padding::new(12.0)).height(Fill);
         if show_tabs {
             content = content.push(tab_bar::view(&self.config.mode, self.current_mode_index));
         }
-        content = content.push(input).push(results);
+        content = content.push(input);
+        if let Some(error) = &self.config_error {
+            content = content.push(text(error.as_str()).size(12).color(theme::TEXT_ERROR));
+        }
+        if self.refreshing {
+            content = content.push(text("Refreshing…").size(12).color(theme::TEXT_SECONDARY));
+        }
+        content = content.push(results);
+        if self.config.window.show_hints {
+            content = content.push(space::vertical());
+            content = content.push(hint_bar::view(&self.key_hints()));
+        }
 
+        let main_style = if self.config.window.blur {
+            theme::main_container_blurred as fn(&Theme) -> container::Style
+        } else {
+            theme::main_container
+        };
         let main = container(content)
             .width(Fill)
             .height(Fill)
-            .style(theme::main_container);
+            .style(main_style);
 
         container(main).width(Fill).height(Fill).into()
     }
@@ -457,7 +800,8 @@ impl State {
     pub fn subscription(&self) -> Subscription<Message> {
         let mut subs = vec![
             hotkey::subscription(self.hotkey_modes.clone()).map(Message::Hotkey),
-            ipc_server::dmenu_subscription(),
+            ipc_server::dmenu_subscription(self.config.dmenu.max_items),
+            signal::subscription().map(|()| Message::Shutdown),
         ];
 
         // Normal mode needs close events to track window lifecycle
@@ -472,37 +816,42 @@ impl State {
             );
         }
 
+        // Clipboard history: NSPasteboard has no change notification, so poll it
+        if self.config.clipboard.history {
+            subs.push(iced::time::every(CLIPBOARD_POLL_INTERVAL).map(|_| Message::ClipboardPoll));
+        }
+
         if self.visible {
-            subs.push(
-                event::listen_with(|event, status, _window| match event {
-                    iced::Event::Keyboard(kb_event) => {
-                        if matches!(status, event::Status::Ignored) {
-                            Some(Message::KeyEvent(kb_event))
-                        } else {
-                            match &kb_event {
-                                keyboard::Event::KeyPressed {
-                                    key:
-                                        keyboard::Key::Named(keyboard::key::Named::Escape),
-                                    ..
-                                } => Some(Message::KeyEvent(kb_event)),
-                                keyboard::Event::KeyPressed {
-                                    key:
-                                        keyboard::Key::Named(keyboard::key::Named::Tab),
-                                    modifiers,
-                                    ..
-                                } if modifiers.control() => Some(Message::KeyEvent(kb_event)),
-                                _ => None,
-                            }
-                        }
+            subs.push(event::listen_with(|event, status, _window| match event {
+                iced::Event::Keyboard(kb_event)
+                    if matches!(status, event::Status::Ignored) || is_app_shortcut(&kb_event) =>
+                {
+                    Some(Message::KeyEvent(kb_event))
+                }
+                _ => None,
+            }));
+
+            // Tick only while the matcher has work (items or query changed)
+            if self.matcher.needs_tick() {
+                subs.push(
+                    iced::time::every(std::time::Duration::from_millis(16))
+                        .map(|_| Message::MatcherTick),
+                );
+            }
+
+            if self.config.window.hide_on_blur {
+                subs.push(event::listen_with(|event, _status, _window| match event {
+                    iced::Event::Window(window::Event::Focused) => Some(Message::WindowFocus(true)),
+                    iced::Event::Window(window::Event::Unfocused) => {
+                        Some(Message::WindowFocus(false))
                     }
                     _ => None,
-                }),
-            );
+                }));
+            }
 
-            subs.push(
-                iced::time::every(std::time::Duration::from_millis(16))
-                    .map(|_| Message::MatcherTick),
-            );
+            if self.idle_timeout().is_some() {
+                subs.push(iced::time::every(Duration::from_secs(1)).map(|_| Message::IdleTimeout));
+            }
         }
 
         Subscription::batch(subs)
@@ -522,6 +871,248 @@ impl State {
 
     // ---- Action execution ----
 
+    /// Run the action of the selected row (or answer the dmenu client), then hide.
+    /// Actions with `keep_open` leave the launcher open and only clear the query.
+    fn execute_selected(&mut self) -> Task<Message> {
+        let eval_count = self.eval_items.len();
+        if self.selected < eval_count {
+            // Selected an evaluator result
+            let Some((config, dmenu_item)) = self.pending_eval_action(self.selected) else {
+                return self.hide();
+            };
+            let history_task = if config.history {
+                let evaluator = self.eval_items[self.selected].provider_name.clone();
+                let query = self.query.clone();
+                let title = dmenu_item.title.clone();
+                let value = dmenu_item.get_field(&config.field);
+                command::record_in_background(move || {
+                    heats_core::history::record(&evaluator, &query, &title, &value);
+                })
+            } else {
+                Task::none()
+            };
+            if config.keep_open {
+                command::run_action(&config, &dmenu_item);
+                let clear_task = self.update(Message::QueryChanged(String::new()));
+                return Task::batch([history_task, clear_task]);
+            }
+            let hide_task = self.hide();
+            command::run_action(&config, &dmenu_item);
+            return Task::batch([history_task, hide_task]);
+        }
+
+        let adjusted = self.selected - eval_count;
+        if let Some(item) = self.results.get(adjusted) {
+            if self.is_dmenu_session {
+                // Alt+Enter (or Alt+click) picks the alternate action
+                let action = if self.modifiers.alt() {
+                    heats_core::ipc::ACTION_ALT
+                } else {
+                    heats_core::ipc::ACTION_DEFAULT
+                };
+                let selection = DmenuSelection {
+                    ids: self.dmenu_selection(item.id),
+                    action,
+                };
+                self.send_dmenu_response(Some(selection));
+            }
+        }
+        // Capture action info before hide() clears state
+        let action = self.pending_action(adjusted);
+        if let Some((provider, dmenu_item)) = action.as_ref().filter(|(p, _)| p.keep_open) {
+            let record_task = command::execute_action(provider, dmenu_item);
+            let clear_task = self.update(Message::QueryChanged(String::new()));
+            return Task::batch([record_task, clear_task]);
+        }
+        // Hide first so macOS deactivates Heats before the action
+        // activates the target app — avoids focus bounce-back
+        let hide_task = self.hide();
+        let record_task = match action {
+            Some((provider, dmenu_item)) => command::execute_action(&provider, &dmenu_item),
+            None => Task::none(),
+        };
+        Task::batch([hide_task, record_task])
+    }
+
+    /// Record the ID of the selected result so the highlight can follow it.
+    fn remember_selection(&mut self) {
+        self.selected_id = self
+            .selected
+            .checked_sub(self.eval_items.len())
+            .and_then(|i| self.results.get(i))
+            .and_then(|item| item.id);
+    }
+
+    /// After results or evaluator rows change, move `selected` to wherever the
+    /// remembered item landed, or clamp it if that item is gone.
+    fn sync_selection(&mut self) {
+        let eval_count = self.eval_items.len();
+        let position = self
+            .selected_id
+            .and_then(|id| self.results.iter().position(|item| item.id == Some(id)));
+        if let Some(position) = position {
+            self.selected = eval_count + position;
+            return;
+        }
+        let total = eval_count + self.results.len();
+        if total > 0 && self.selected >= total {
+            self.selected = total - 1;
+        }
+    }
+
+    /// Cmd+Shift+C: copy the selected row's field value (or its title) without running the action.
+    /// In dmenu sessions this is the row's title.
+    fn copy_selected(&self) -> Task<Message> {
+        let eval_count = self.eval_items.len();
+        let value = if self.selected < eval_count {
+            self.pending_eval_action(self.selected)
+                .map(|(config, dmenu_item)| dmenu_item.get_field(&config.field))
+        } else {
+            let adjusted = self.selected - eval_count;
+            self.pending_action(adjusted)
+                .map(|(provider, dmenu_item)| provider.field_value(dmenu_item))
+                .filter(|value| !value.is_empty())
+                .or_else(|| self.results.get(adjusted).map(|item| item.title.clone()))
+        };
+        if let Some(value) = value {
+            tracing::debug!("Copying selected item to clipboard");
+            heats_core::platform::macos::copy_to_clipboard(&value);
+        }
+        Task::none()
+    }
+
+    /// Store a lazily loaded icon on every copy of the item with `id`.
+    fn set_icon(&mut self, id: usize, icon: IconData) {
+        let cached = self.provider_cache.values_mut().flatten();
+        for li in self.loaded_items.iter_mut().chain(cached) {
+            if li.item.id == Some(id) {
+                li.item.icon = Some(icon.clone());
+            }
+        }
+        for item in self.all_items.iter_mut().chain(self.results.iter_mut()) {
+            if item.id == Some(id) {
+                Arc::make_mut(item).icon = Some(icon.clone());
+            }
+        }
+    }
+
+    /// Copy icons that arrived after the items were handed to the matcher onto `results`.
+    fn attach_icons(&mut self) {
+        let icons: HashMap<usize, &IconData> = self
+            .loaded_items
+            .iter()
+            .filter_map(|li| Some((li.item.id?, li.item.icon.as_ref()?)))
+            .collect();
+        for item in self.results.iter_mut().filter(|item| item.icon.is_none()) {
+            if let Some(icon) = item.id.and_then(|id| icons.get(&id)) {
+                Arc::make_mut(item).icon = Some((*icon).clone());
+            }
+        }
+    }
+
+    /// Evaluator results (at top) followed by provider results, as rendered.
+    fn display_items(&self) -> Vec<&SourceItem> {
+        self.eval_items
+            .iter()
+            .map(|li| &li.item)
+            .chain(self.results.iter().map(Arc::as_ref))
+            .collect()
+    }
+
+    fn list_options(&self) -> result_list::ListOptions<'_> {
+        let window = &self.config.window;
+        result_list::ListOptions {
+            window_height: window.height,
+            has_tabs: self.show_tabs(),
+            has_hints: window.show_hints,
+            eval_count: self.eval_items.len(),
+            labels: &window.source_labels,
+            show_source_tag: window.show_source_tag,
+            group_by_source: window.group_by_source,
+            marked: &self.dmenu_marked,
+            pinned: &self.pinned_ids,
+            row_padding: window.row_padding.max(0.0),
+            hovered: self.hovered,
+        }
+    }
+
+    /// Keys shown in the `show_hints` footer for the current session.
+    fn key_hints(&self) -> Vec<(&'static str, &'static str)> {
+        let mut hints = Vec::new();
+        if self.is_dmenu_session {
+            hints.push(("↵", "select"));
+            if self.dmenu_context.multi {
+                hints.push(("⇥", "mark"));
+            }
+        } else {
+            hints.push(("↵", "open"));
+            hints.push(("⌘R", "refresh"));
+            if !self.nav_history.is_empty() {
+                hints.push(("⌘[", "back"));
+            }
+            if self.show_tabs() {
+                hints.push(("⌃⇥", "mode"));
+            }
+        }
+        hints.push(("⌘⇧C", "copy"));
+        hints.push(("esc", "cancel"));
+        hints
+    }
+
+    /// Order freshly matched `results` for display: pinned items first, then
+    /// (with `group_by_source`) grouped by the mode's provider order.
+    fn arrange_results(&mut self) {
+        self.pin_results();
+        if !self.config.window.group_by_source || self.is_dmenu_session {
+            return;
+        }
+        let Some(index) = self.current_mode_index else {
+            return;
+        };
+        // Stable sort: keeps pinned-first and the matcher's order within each group
+        let providers = &self.config.mode[index].providers;
+        self.results.sort_by_key(|item| {
+            providers
+                .iter()
+                .position(|name| *name == item.source_name)
+                .unwrap_or(usize::MAX)
+        });
+    }
+
+    /// Move the current mode's `pinned` items to the front of `results`, keeping the
+    /// matcher's order within both groups. Pinned items that don't match stay hidden.
+    fn pin_results(&mut self) {
+        self.pinned_ids.clear();
+        let Some(index) = self.current_mode_index.filter(|_| !self.is_dmenu_session) else {
+            return;
+        };
+        let pinned = &self.config.mode[index].pinned;
+        if pinned.is_empty() {
+            return;
+        }
+
+        let providers = &self.config.provider;
+        let pinned_ids: HashSet<usize> = self
+            .loaded_items
+            .iter()
+            .filter(|li| {
+                pinned.contains(&li.item.title)
+                    || providers
+                        .get(&li.provider_name)
+                        .is_some_and(|p| pinned.contains(&p.field_value(&li.dmenu_item)))
+            })
+            .filter_map(|li| li.item.id)
+            .collect();
+
+        let (mut front, back): (Vec<Arc<SourceItem>>, Vec<Arc<SourceItem>>) =
+            std::mem::take(&mut self.results)
+                .into_iter()
+                .partition(|item| item.id.is_some_and(|id| pinned_ids.contains(&id)));
+        front.extend(back);
+        self.results = front;
+        self.pinned_ids = pinned_ids;
+    }
+
     /// Extract action info (provider config + dmenu item) for the selected index,
     /// returning owned copies so they survive hide()/reset_state().
     fn pending_action(
@@ -531,16 +1122,90 @@ impl State {
         if self.is_dmenu_session {
             return None;
         }
-        let selected_item = self.results.get(selected_index)?;
-        let loaded = self.loaded_items.iter().find(|li| {
-            li.item.title == selected_item.title
-                && li.item.source_name == selected_item.source_name
-                && li.item.exec_path == selected_item.exec_path
-        })?;
+        let selected_id = self.results.get(selected_index)?.id?;
+        let loaded = self
+            .loaded_items
+            .iter()
+            .find(|li| li.item.id == Some(selected_id))?;
         let provider = self.config.provider.get(&loaded.provider_name)?;
         Some((provider.clone(), loaded.dmenu_item.clone()))
     }
 
+    /// Text for the preview pane: the output of the provider's `preview` command, the
+    /// selected item's `preview_field`, or the whole item as pretty-printed JSON. None
+    /// when the pane is off or the row has no structured data (dmenu items are plain lines).
+    fn preview_content(&self) -> Option<String> {
+        if !self.config.window.preview || self.is_dmenu_session {
+            return None;
+        }
+        let eval_count = self.eval_items.len();
+        if self.selected < eval_count {
+            let item = &self.eval_items[self.selected].dmenu_item;
+            return serde_json::to_string_pretty(item).ok();
+        }
+        let loaded = self.selected_loaded_item()?;
+        let provider = self.config.provider.get(&loaded.provider_name);
+        if provider.is_some_and(|provider| !provider.preview.is_empty()) {
+            return match &self.preview_output {
+                Some((id, output)) if loaded.item.id == Some(*id) => Some(output.clone()),
+                _ => Some(String::new()),
+            };
+        }
+        match provider.and_then(|provider| provider.preview_field.as_deref()) {
+            Some(field) => Some(loaded.dmenu_item.get_field(field)),
+            None => serde_json::to_string_pretty(&loaded.dmenu_item).ok(),
+        }
+    }
+
+    /// The provider item behind the selected row (None for evaluator rows).
+    fn selected_loaded_item(&self) -> Option<&LoadedItem> {
+        let index = self.selected.checked_sub(self.eval_items.len())?;
+        let id = self.results.get(index)?.id?;
+        self.loaded_items.iter().find(|li| li.item.id == Some(id))
+    }
+
+    /// Start the selected item's provider `preview` command after a short debounce,
+    /// unless its output is already shown or on the way.
+    fn schedule_preview(&mut self) -> Task<Message> {
+        if !self.config.window.preview || self.is_dmenu_session {
+            return Task::none();
+        }
+        let Some(loaded) = self.selected_loaded_item() else {
+            return Task::none();
+        };
+        let Some(id) = loaded.item.id else {
+            return Task::none();
+        };
+        let Some(provider) = self
+            .config
+            .provider
+            .get(&loaded.provider_name)
+            .filter(|provider| !provider.preview.is_empty())
+        else {
+            return Task::none();
+        };
+        let shown = matches!(&self.preview_output, Some((shown, _)) if *shown == id);
+        let pending = matches!(&self.preview_pending, Some((pending, _)) if *pending == id);
+        if shown || pending {
+            return Task::none();
+        }
+
+        let preview = provider.preview.clone();
+        let args = provider.field_values(&loaded.dmenu_item);
+        let env = provider.env.clone();
+        let (task, handle) = Task::perform(
+            async move {
+                tokio::time::sleep(PREVIEW_DEBOUNCE).await;
+                command::run_preview(&preview, &args, &env).await
+            },
+            move |output| Message::PreviewLoaded { id, output },
+        )
+        .abortable();
+        // Dropping the previous handle aborts its run
+        self.preview_pending = Some((id, handle.abort_on_drop()));
+        task
+    }
+
     /// Extract evaluator action info for the selected eval index.
     fn pending_eval_action(
         &self,
@@ -551,42 +1216,133 @@ impl State {
         Some((config.clone(), loaded.dmenu_item.clone()))
     }
 
+    /// Execute the n-th row (0-based) of the currently visible scroll window.
+    /// Positions beyond the visible rows are ignored.
+    fn execute_visible(&mut self, position: usize) -> Task<Message> {
+        let display_items = self.display_items();
+        let range = result_list::visible_range(self.selected, &display_items, &self.list_options());
+        let index = range.start + position;
+        if index >= range.end {
+            return Task::none();
+        }
+        self.update(Message::SelectAndExecute(index))
+    }
+
     // ---- Dmenu ----
 
     fn start_dmenu_session(
         &mut self,
         items: Vec<SourceItem>,
-        tx: Option<oneshot::Sender<Option<usize>>>,
+        context: IpcContext,
+        tx: Option<oneshot::Sender<Option<DmenuSelection>>>,
     ) {
         self.dmenu_tx = tx;
         self.is_dmenu_session = true;
-
+        self.dmenu_context = context;
+
+        // Scripts often put key details in the subtitle, so make it searchable too.
+        // Item ids (raw line indices) are unaffected by the haystack.
+        let items: Vec<Arc<SourceItem>> = items.into_iter().map(Arc::new).collect();
+        self.matcher.set_items_with_subtitle(items.clone());
+        // Only the first rows are shown until the first MatcherTick fills `results`
+        self.results = items.iter().take(DMENU_INITIAL_ROWS).cloned().collect();
         self.all_items = items;
-        self.results = self.all_items.clone();
-        self.matcher.set_items(self.all_items.clone());
+
+        // Pre-filled query: results are filtered from the first MatcherTick
+        if let Some(query) = self.dmenu_context.query.clone() {
+            self.matcher.update_query(&query);
+            self.query = query;
+        }
     }
 
     fn show_dmenu(&mut self) -> Task<Message> {
         self.visible = true;
+        self.focused_since_show = false;
+        self.last_input = Instant::now();
+        self.run_hook(self.config.on_show.as_deref());
         tracing::debug!(
             "show_dmenu: results={}, all_items={}",
             self.results.len(),
             self.all_items.len()
         );
 
+        // Keep the caret after a pre-filled query
+        let cursor_task =
+            iced::widget::operation::move_cursor_to_end(search_input::SEARCH_INPUT_ID);
+
         match self.config.window.mode {
-            WindowMode::Fixed => self.show_fixed(Task::none()),
-            WindowMode::Normal => self.show_normal(Task::none()),
+            WindowMode::Fixed => self.show_fixed(cursor_task),
+            WindowMode::Normal => self.show_normal(cursor_task),
+        }
+    }
+
+    /// Item IDs to report: the marked items if any, otherwise the chosen one
+    fn dmenu_selection(&self, chosen: Option<usize>) -> Vec<usize> {
+        if self.dmenu_marked.is_empty() {
+            chosen.into_iter().collect()
+        } else {
+            self.dmenu_marked.clone()
         }
     }
 
-    fn send_dmenu_response(&mut self, response: Option<usize>) {
+    /// Tab: replace the query with the selected row's title, keeping the launcher open.
+    fn complete_query(&mut self) -> Task<Message> {
+        let Some(title) = self
+            .display_items()
+            .get(self.selected)
+            .map(|item| item.title.clone())
+        else {
+            return Task::none();
+        };
+        if title == self.query {
+            return Task::none();
+        }
+        let query_task = self.update(Message::QueryChanged(title));
+        let cursor_task =
+            iced::widget::operation::move_cursor_to_end(search_input::SEARCH_INPUT_ID);
+        Task::batch([query_task, cursor_task])
+    }
+
+    /// Tab in a `multi` session: toggle the mark on the selected item and move down
+    fn toggle_dmenu_mark(&mut self) -> Task<Message> {
+        let eval_count = self.eval_items.len();
+        let id = self
+            .selected
+            .checked_sub(eval_count)
+            .and_then(|i| self.results.get(i))
+            .and_then(|item| item.id);
+        if let Some(id) = id {
+            match self.dmenu_marked.iter().position(|&marked| marked == id) {
+                Some(pos) => {
+                    self.dmenu_marked.remove(pos);
+                }
+                None => self.dmenu_marked.push(id),
+            }
+        }
+        if self.selected + 1 < eval_count + self.results.len() {
+            self.selected += 1;
+        }
+        self.remember_selection();
+        Task::none()
+    }
+
+    fn send_dmenu_response(&mut self, response: Option<DmenuSelection>) {
         if let Some(tx) = self.dmenu_tx.take() {
             let _ = tx.send(response);
         }
         self.is_dmenu_session = false;
     }
 
+    /// `--auto-select`: the user has typed something and matching settled on a single item
+    fn should_auto_select(&self) -> bool {
+        self.is_dmenu_session
+            && self.dmenu_context.auto_select
+            && !self.query.is_empty()
+            && !self.matcher.is_running()
+            && self.eval_items.is_empty()
+            && self.results.len() == 1
+    }
+
     fn cancel_dmenu_session(&mut self) {
         if self.is_dmenu_session {
             // Send None (cancelled) to the client
@@ -614,13 +1370,18 @@ impl State {
         }
 
         self.visible = true;
+        self.focused_since_show = false;
+        self.last_input = Instant::now();
+        self.run_hook(self.config.on_show.as_deref());
 
         // Split providers into cached (instant) and uncached (need async load)
         let mut cached_items: Vec<LoadedItem> = Vec::new();
         let mut uncached_names: Vec<String> = Vec::new();
 
         for name in &provider_names {
-            if let Some(items) = self.provider_cache.get(name) {
+            if self.is_on_query(name) {
+                self.deferred_providers.push(name.clone());
+            } else if let Some(items) = self.provider_cache.get(name) {
                 cached_items.extend(items.clone());
             } else {
                 uncached_names.push(name.clone());
@@ -630,9 +1391,14 @@ impl State {
         // Pre-populate with cached items immediately
         if !cached_items.is_empty() {
             self.loaded_items = cached_items;
-            self.all_items = self.loaded_items.iter().map(|li| li.item.clone()).collect();
+            self.all_items = self
+                .loaded_items
+                .iter()
+                .map(|li| Arc::new(li.item.clone()))
+                .collect();
             self.matcher.set_items(self.all_items.clone());
             self.results = self.all_items.clone();
+            self.arrange_results();
         }
 
         // Load uncached providers asynchronously (if any)
@@ -652,7 +1418,87 @@ impl State {
         }
     }
 
+    /// Install the native blur backing on a newly created window (`window.blur`)
+    fn apply_window_blur(&self) {
+        if self.config.window.blur {
+            heats_core::platform::macos::install_window_blur(theme::CORNER_RADIUS as f64);
+        }
+    }
+
+    /// Tick the matcher and, if its matches changed, rebuild `results` from them.
+    fn refresh_results(&mut self) {
+        if !self.matcher.tick() {
+            return;
+        }
+        self.results = if self.matcher.query_is_empty() {
+            // Shares the items' `Arc`s, so this stays cheap for large lists
+            self.all_items.clone()
+        } else if let Some(min_score) = self.config.matcher.min_score {
+            self.matcher.results_with_threshold(50, min_score)
+        } else {
+            self.matcher.results(50)
+        };
+        self.attach_icons();
+        self.arrange_results();
+        self.sync_selection();
+    }
+
+    /// Follow-up once the displayed results changed: `--auto-select` and `auto_height`.
+    fn results_settled(&mut self) -> Task<Message> {
+        if self.should_auto_select() {
+            tracing::debug!("Auto-selecting the only dmenu match");
+            self.selected = 0;
+            return self.update(Message::Execute);
+        }
+        let preview_task = self.schedule_preview();
+        Task::batch([self.fit_window_height(), preview_task])
+    }
+
+    /// `auto_height`: resize the window to fit the current rows, if that changed its height.
+    fn fit_window_height(&mut self) -> Task<Message> {
+        if !self.config.window.auto_height || !self.visible {
+            return Task::none();
+        }
+        let Some(id) = self.window_id else {
+            return Task::none();
+        };
+        let display_items = self.display_items();
+        let height = result_list::fit_height(&display_items, &self.list_options());
+        if (height - self.window_height).abs() < 0.5 {
+            return Task::none();
+        }
+        self.window_height = height;
+
+        let width = self.config.window.width;
+        match self.config.window.mode {
+            WindowMode::Fixed => {
+                heats_core::platform::macos::native_resize_window(width as f64, height as f64);
+                Task::none()
+            }
+            WindowMode::Normal => window::resize(id, Size::new(width, height)),
+        }
+    }
+
+    /// Hide the launcher if it's shown, otherwise show `mode_name`.
+    fn toggle(&mut self, mode_name: &str) -> Task<Message> {
+        if self.visible {
+            self.hide()
+        } else {
+            // If a dmenu session is active, cancel it before showing
+            if self.is_dmenu_session {
+                self.cancel_dmenu_session();
+                self.reset_state();
+            }
+            self.show_mode(mode_name)
+        }
+    }
+
     fn hide(&mut self) -> Task<Message> {
+        if self.visible {
+            self.record_navigation();
+            self.run_hook(self.config.on_hide.as_deref());
+            self.config_error = None;
+        }
         self.visible = false;
         // If this is a dmenu session, cancel it (send None to client)
         self.cancel_dmenu_session();
@@ -667,15 +1513,17 @@ impl State {
     // -- Normal mode: open/close window each time --
 
     fn show_normal(&mut self, load_task: Task<Message>) -> Task<Message> {
-        let disp_bounds = heats_core::platform::macos::focused_display_bounds();
+        let disp_bounds = heats_core::platform::macos::mouse_display_bounds();
         let pos = Self::center_on_display(
             &disp_bounds,
             self.config.window.width,
             self.config.window.height,
+            self.config.window.y_ratio(),
         );
         tracing::debug!("show_normal: disp_bounds={:?}, pos={:?}", disp_bounds, pos);
+        self.window_height = self.config.window.height;
 
-        let (_id, open_task) = window::open(window::Settings {
+        let (id, open_task) = window::open(window::Settings {
             size: Size::new(self.config.window.width, self.config.window.height),
             position: window::Position::Specific(pos),
             visible: true,
@@ -687,10 +1535,14 @@ impl State {
             ..window::Settings::default()
         });
 
+        self.opening_window = Some(id);
+
         Task::batch([open_task.map(Message::WindowOpened), load_task])
     }
 
     fn hide_normal(&mut self) -> Task<Message> {
+        // A window still opening is closed once its `WindowOpened` arrives
+        self.opening_window = None;
         if let Some(id) = self.window_id.take() {
             window::close(id)
         } else {
@@ -706,38 +1558,84 @@ impl State {
             None => return Task::none(),
         };
 
+        // Re-resolve the named display so a monitor plugged in or out since boot
+        // doesn't leave the window on a screen that no longer exists
+        if !self.config.window.display.is_empty() {
+            self.fixed_display = Self::resolve_fixed_display(&self.config.window.display);
+        }
+
         // Use native NSWindow API to position and show the window.
         // This bypasses winit's coordinate handling and avoids AeroSpace interference.
         heats_core::platform::macos::native_show_window(
             &self.fixed_display,
             self.config.window.width as f64,
             self.config.window.height as f64,
+            self.config.window.y_ratio(),
+            self.config.window.fade_ms,
         );
+        self.window_height = self.config.window.height;
+        let resize_task = self.fit_window_height();
 
         // Still use iced's focus APIs for input handling
         let focus = window::gain_focus::<Message>(id)
             .chain(iced::widget::operation::focus(search_input::SEARCH_INPUT_ID));
 
-        Task::batch([focus, load_task])
+        Task::batch([focus, load_task, resize_task])
     }
 
     fn hide_fixed(&self) -> Task<Message> {
         // Use native NSWindow.orderOut to truly hide the window.
         // Unlike moving off-screen, this is invisible to window managers.
-        heats_core::platform::macos::native_hide_window();
+        heats_core::platform::macos::native_hide_window(self.config.window.fade_ms);
         Task::none()
     }
 
     // ---- Helpers ----
 
+    /// Configured idle timeout, or None when disabled or a dmenu script is waiting.
+    fn idle_timeout(&self) -> Option<Duration> {
+        if self.is_dmenu_session {
+            return None;
+        }
+        self.config
+            .window
+            .idle_timeout_secs
+            .map(Duration::from_secs)
+    }
+
+    /// Whether the mode tab bar is shown (built-in sessions with multiple modes).
+    fn show_tabs(&self) -> bool {
+        !self.is_dmenu_session && self.config.mode.len() > 1
+    }
+
+    /// Spawn an on_show/on_hide hook if one is configured.
+    fn run_hook(&self, hook: Option<&[String]>) {
+        if let Some(hook) = hook {
+            command::spawn_hook(hook);
+        }
+    }
+
+    /// Bounds of the configured fixed-mode display, or of the keyboard-focused display
+    /// when no name is configured or the named display isn't connected.
+    fn resolve_fixed_display(name: &str) -> (f64, f64, f64, f64) {
+        if name.is_empty() {
+            return heats_core::platform::macos::focused_display_bounds();
+        }
+        heats_core::platform::macos::display_bounds_by_name(name).unwrap_or_else(|| {
+            tracing::info!("Display \"{name}\" not connected, using the focused display");
+            heats_core::platform::macos::focused_display_bounds()
+        })
+    }
+
     fn center_on_display(
         display: &(f64, f64, f64, f64),
         win_w: f32,
         win_h: f32,
+        y_ratio: f64,
     ) -> Point {
         let (disp_x, disp_y, disp_w, disp_h) = *display;
         let x = disp_x + (disp_w - win_w as f64) / 2.0;
-        let y = disp_y + (disp_h - win_h as f64) / 3.0;
+        let y = disp_y + (disp_h - win_h as f64) * y_ratio;
         Point::new(x as f32, y as f32)
     }
 
@@ -755,18 +1653,137 @@ impl State {
             return Task::none();
         }
 
-        self.current_mode_index = Some(new_index);
-        let mode = &self.config.mode[new_index];
+        self.load_mode(new_index)
+    }
+
+    /// Reopen the most recently hidden (mode, query) from the navigation history.
+    fn navigate_back(&mut self) -> Task<Message> {
+        if !self.visible || self.is_dmenu_session {
+            return Task::none();
+        }
+
+        let (mode_name, query) = match self.nav_history.pop() {
+            Some(entry) => entry,
+            None => return Task::none(),
+        };
+        let index = match self.config.mode.iter().position(|m| m.name == mode_name) {
+            Some(i) => i,
+            None => return Task::none(),
+        };
+
+        let load_task = self.load_mode(index);
+        let query_task = self.update(Message::QueryChanged(query));
+        Task::batch([load_task, query_task])
+    }
+
+    /// Re-run every provider of the current mode, bypassing the cache, and keep the query.
+    fn refresh_current_mode(&mut self) -> Task<Message> {
+        if !self.visible || self.is_dmenu_session {
+            return Task::none();
+        }
+        let index = match self.current_mode_index {
+            Some(i) => i,
+            None => return Task::none(),
+        };
+
+        let provider_names = self.config.mode[index].providers.clone();
+        // Counted as fresh so the background refresh doesn't also run them meanwhile
+        let now = Instant::now();
+        for name in &provider_names {
+            self.provider_cache.remove(name);
+            self.cache_last_updated.insert(name.clone(), now);
+        }
+        self.refreshing = provider_names.iter().any(|name| !self.is_on_query(name));
+        tracing::info!(
+            "Refreshing providers for mode '{}'",
+            self.config.mode[index].name
+        );
+
+        let query = std::mem::take(&mut self.query);
+        // Runs each source once: `ItemsLoaded` refills the cleared caches from the results
+        let load_task = self.load_mode(index);
+        let query_task = if query.is_empty() {
+            Task::none()
+        } else {
+            self.update(Message::QueryChanged(query))
+        };
+        Task::batch([load_task, query_task])
+    }
+
+    /// Remember the current mode and query so Cmd+[ can return to them after hiding.
+    fn record_navigation(&mut self) {
+        if self.is_dmenu_session {
+            return;
+        }
+        let mode_name = match self.current_mode_index {
+            Some(i) => self.config.mode[i].name.clone(),
+            None => return,
+        };
+        let entry = (mode_name, self.query.clone());
+        if self.nav_history.last() == Some(&entry) {
+            return;
+        }
+        self.nav_history.push(entry);
+        if self.nav_history.len() > NAV_HISTORY_LIMIT {
+            self.nav_history.remove(0);
+        }
+    }
+
+    /// Order loaded items by their provider's position in the current mode
+    /// (stable, so each provider keeps its own item order).
+    fn sort_by_provider_order(&mut self) {
+        let providers = match self.current_mode_index {
+            Some(i) => &self.config.mode[i].providers,
+            None => return,
+        };
+        self.loaded_items.sort_by_key(|li| {
+            providers
+                .iter()
+                .position(|name| *name == li.provider_name)
+                .unwrap_or(usize::MAX)
+        });
+    }
+
+    /// Whether a provider waits for the first keystroke before loading.
+    fn is_on_query(&self, name: &str) -> bool {
+        self.config
+            .provider
+            .get(name)
+            .is_some_and(|p| p.trigger == Trigger::OnQuery)
+    }
+
+    /// Load the current mode's `on_query` providers once the query becomes non-empty.
+    /// Their items are merged into the already-loaded ones via `ItemsLoaded`.
+    fn load_deferred_providers(&mut self, query: &str) -> Task<Message> {
+        if query.is_empty() || self.deferred_providers.is_empty() {
+            return Task::none();
+        }
+        let names = std::mem::take(&mut self.deferred_providers);
+        let providers = self.config.provider.clone();
+        let query = query.to_string();
+        Task::perform(
+            async move { command::load_from_providers_with_query(&names, &providers, &query).await },
+            Message::ItemsLoaded,
+        )
+    }
+
+    /// Switch the open launcher to the mode at `index`, resetting the query and reloading items.
+    fn load_mode(&mut self, index: usize) -> Task<Message> {
+        self.current_mode_index = Some(index);
+        let mode = &self.config.mode[index];
 
         // Reset query and results
         self.query.clear();
         self.selected = 0;
+        self.selected_id = None;
         self.all_items.clear();
         self.results.clear();
+        self.pinned_ids.clear();
         self.loaded_items.clear();
-        self.matcher = Matcher::new();
+        self.matcher = Matcher::new(&self.config.matcher);
         self.eval_items.clear();
         self.eval_generation = 0;
+        self.deferred_providers.clear();
 
         // Set evaluators for new mode
         self.active_evaluators = mode.evaluators.clone();
@@ -777,7 +1794,9 @@ impl State {
         let mut uncached_names: Vec<String> = Vec::new();
 
         for name in &provider_names {
-            if let Some(items) = self.provider_cache.get(name) {
+            if self.is_on_query(name) {
+                self.deferred_providers.push(name.clone());
+            } else if let Some(items) = self.provider_cache.get(name) {
                 cached_items.extend(items.clone());
             } else {
                 uncached_names.push(name.clone());
@@ -786,9 +1805,14 @@ impl State {
 
         if !cached_items.is_empty() {
             self.loaded_items = cached_items;
-            self.all_items = self.loaded_items.iter().map(|li| li.item.clone()).collect();
+            self.all_items = self
+                .loaded_items
+                .iter()
+                .map(|li| Arc::new(li.item.clone()))
+                .collect();
             self.matcher.set_items(self.all_items.clone());
             self.results = self.all_items.clone();
+            self.arrange_results();
         }
 
         let load_task = if uncached_names.is_empty() {
@@ -810,16 +1834,53 @@ impl State {
     fn reset_state(&mut self) {
         self.query.clear();
         self.selected = 0;
+        self.selected_id = None;
         self.results.clear();
+        self.pinned_ids.clear();
         self.loaded_items.clear();
         self.matcher.update_query("");
         self.eval_items.clear();
         self.eval_generation = 0;
         self.active_evaluators.clear();
         self.current_mode_index = None;
+        self.deferred_providers.clear();
+        self.refreshing = false;
+        self.dmenu_context = IpcContext::default();
+        self.dmenu_marked.clear();
+        self.preview_output = None;
+        self.preview_pending = None;
+        self.hovered = None;
+        self.modifiers = keyboard::Modifiers::default();
+        self.execute_pending = false;
         // provider_cache is intentionally NOT cleared — persists across show/hide
     }
 
+    // ---- Clipboard history ----
+
+    /// Add the pasteboard's text to the clipboard history if it changed since last time.
+    fn record_clipboard(&mut self) -> Task<Message> {
+        let count = heats_core::platform::macos::pasteboard_change_count();
+        if self.clipboard_change_count == Some(count) {
+            return Task::none();
+        }
+        self.clipboard_change_count = Some(count);
+        // Password managers mark their copies as concealed or transient: never store those
+        if heats_core::platform::macos::pasteboard_is_concealed() {
+            tracing::debug!("Clipboard history: skipping a concealed copy");
+            return Task::none();
+        }
+        // Non-text copies (images, files) and whitespace-only text aren't recorded
+        match heats_core::platform::macos::pasteboard_string() {
+            Some(text) if !text.trim().is_empty() => {
+                let max_entries = self.config.clipboard.max_entries;
+                command::record_in_background(move || {
+                    heats_core::clipboard::record(&text, max_entries);
+                })
+            }
+            _ => Task::none(),
+        }
+    }
+
     // ---- Background cache ----
 
     /// Returns the minimum cache_interval across all providers (for the subscription timer).
@@ -827,33 +1888,49 @@ impl State {
         self.config
             .provider
             .values()
+            .filter(|p| p.trigger == Trigger::OnOpen)
             .filter_map(|p| p.cache_interval)
             .min()
             .map(Duration::from_secs)
     }
 
-    /// Kick initial cache load for all providers with cache_interval set.
-    fn initial_cache_load(&self) -> Task<Message> {
-        let tasks: Vec<Task<Message>> = self
-            .config
-            .provider
-            .iter()
-            .filter(|(_, p)| p.cache_interval.is_some())
-            .map(|(name, p)| {
-                let name = name.clone();
-                let name_for_msg = name.clone();
-                let providers = HashMap::from([(name.clone(), p.clone())]);
-                Task::perform(
-                    async move {
-                        command::load_from_providers(&[name], &providers).await
-                    },
-                    move |items| Message::CacheUpdated {
-                        provider_name: name_for_msg,
-                        items,
-                    },
-                )
-            })
-            .collect();
+    /// Restore cached providers from disk. Providers whose cache is still within
+    /// `cache_interval` are marked fresh so `refresh_stale_caches` skips them.
+    fn restore_disk_caches(&mut self) -> Task<Message> {
+        let mut tasks = Vec::new();
+        for (name, p) in &self.config.provider {
+            let interval = match p.cache_interval {
+                Some(secs) if p.trigger == Trigger::OnOpen => Duration::from_secs(secs),
+                _ => continue,
+            };
+            let entry = match heats_core::cache::read(name) {
+                Some(entry) => entry,
+                None => continue,
+            };
+
+            let age = entry.age();
+            if age < interval {
+                if let Some(loaded_at) = Instant::now().checked_sub(age) {
+                    self.cache_last_updated.insert(name.clone(), loaded_at);
+                }
+            }
+            tracing::debug!(
+                "Restoring {} cached items for provider '{}' (age {:?})",
+                entry.items.len(),
+                name,
+                age
+            );
+
+            let name = name.clone();
+            let name_for_msg = name.clone();
+            tasks.push(Task::perform(
+                command::restore_cached(name, p.clone(), entry.items),
+                move |items| Message::CacheRestored {
+                    provider_name: name_for_msg,
+                    items,
+                },
+            ));
+        }
 
         if tasks.is_empty() {
             Task::none()
@@ -862,6 +1939,39 @@ impl State {
         }
     }
 
+    /// Store freshly loaded items of cached providers that have no cache yet (first
+    /// load, or cleared by Cmd+R), so the background refresh doesn't run them again.
+    fn cache_loaded_items(&mut self, loaded_items: &[LoadedItem]) -> Task<Message> {
+        let mut by_provider: HashMap<&str, Vec<LoadedItem>> = HashMap::new();
+        for li in loaded_items {
+            by_provider
+                .entry(li.provider_name.as_str())
+                .or_default()
+                .push(li.clone());
+        }
+
+        let mut tasks = Vec::new();
+        for (name, items) in by_provider {
+            let cached = self
+                .config
+                .provider
+                .get(name)
+                .is_some_and(|p| p.trigger == Trigger::OnOpen && p.cache_interval.is_some());
+            if !cached || self.provider_cache.contains_key(name) {
+                continue;
+            }
+            let name = name.to_string();
+            let (write_name, write_items) = (name.clone(), items.clone());
+            tasks.push(
+                Task::future(async move { command::write_cache(write_name, &write_items).await })
+                    .discard(),
+            );
+            self.provider_cache.insert(name.clone(), items);
+            self.cache_last_updated.insert(name, Instant::now());
+        }
+        Task::batch(tasks)
+    }
+
     /// Check each cached provider and refresh if stale.
     fn refresh_stale_caches(&self) -> Task<Message> {
         let now = Instant::now();
@@ -869,6 +1979,7 @@ impl State {
             .config
             .provider
             .iter()
+            .filter(|(_, p)| p.trigger == Trigger::OnOpen)
             .filter_map(|(name, p)| {
                 let interval = Duration::from_secs(p.cache_interval?);
                 let is_stale = self
@@ -887,7 +1998,9 @@ impl State {
                 let providers = HashMap::from([(name.clone(), p)]);
                 Task::perform(
                     async move {
-                        command::load_from_providers(&[name], &providers).await
+                        let items = command::load_from_providers(&[name.clone()], &providers).await;
+                        command::write_cache(name, &items).await;
+                        items
                     },
                     move |items| Message::CacheUpdated {
                         provider_name: name_for_msg,
@@ -904,3 +2017,54 @@ impl State {
         }
     }
 }
+
+/// Load the icons of freshly loaded items in the background, one `IconLoaded` each.
+/// High-resolution variants are used while any connected display is Retina.
+fn load_icons(items: &[LoadedItem]) -> Task<Message> {
+    let retina = heats_core::platform::macos::max_backing_scale_factor() >= 2.0;
+    Task::run(command::icon_stream(items, retina), |(id, icon)| {
+        Message::IconLoaded { id, icon }
+    })
+}
+
+/// Key presses the app handles even when the focused search input captured them.
+fn is_app_shortcut(kb_event: &keyboard::Event) -> bool {
+    match kb_event {
+        keyboard::Event::KeyPressed {
+            key: keyboard::Key::Named(keyboard::key::Named::Escape),
+            ..
+        } => true,
+        keyboard::Event::KeyPressed {
+            key: keyboard::Key::Named(keyboard::key::Named::Tab),
+            ..
+        } => true,
+        keyboard::Event::KeyPressed {
+            key: keyboard::Key::Character(c),
+            modifiers,
+            ..
+        } if matches!(c.as_str(), "[" | "r") => modifiers.command(),
+        keyboard::Event::KeyPressed {
+            key: keyboard::Key::Character(c),
+            modifiers,
+            ..
+        } if is_copy_item(c, *modifiers) => true,
+        keyboard::Event::KeyPressed { key, modifiers, .. } => {
+            modifiers.command() && digit_shortcut(key).is_some()
+        }
+        _ => false,
+    }
+}
+
+/// Cmd+Shift+C copies the selected item. Plain Cmd+C is left to the search input, so a
+/// selected part of the query can still be copied.
+fn is_copy_item(c: &str, modifiers: keyboard::Modifiers) -> bool {
+    modifiers.command() && modifiers.shift() && c.eq_ignore_ascii_case("c")
+}
+
+/// Map `1`–`9` to the digit used by the Cmd+N result shortcuts.
+fn digit_shortcut(key: &keyboard::Key) -> Option<usize> {
+    match key.as_ref() {
+        keyboard::Key::Character(c) => c.parse::<usize>().ok().filter(|n| (1..=9).contains(n)),
+        _ => None,
+    }
+}
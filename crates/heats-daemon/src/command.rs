@@ -1,12 +1,14 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::PathBuf;
 use std::process::Stdio;
+use std::sync::atomic::{AtomicUsize, Ordering};
 
-use tokio::io::{AsyncBufReadExt, BufReader};
-use tokio::process::Command;
+use iced::futures::SinkExt;
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::process::{Child, Command};
 
 use crate::icon;
-use heats_core::config::{EvaluatorConfig, InputMode, ProviderConfig};
+use heats_core::config::{EnvConfig, EvaluatorConfig, InputMode, ProviderConfig};
 use heats_core::source::{DmenuItem, IconData, SourceItem};
 
 /// A loaded item with metadata for action resolution
@@ -20,159 +22,445 @@ pub struct LoadedItem {
     pub dmenu_item: DmenuItem,
 }
 
+/// Source of `SourceItem::id` for provider items. Process-wide so that items from
+/// different loads (and caches) never share an id.
+static NEXT_ITEM_ID: AtomicUsize = AtomicUsize::new(0);
+
 /// Spawn source commands for the given providers in parallel and collect their JSONL output.
 /// Each source command is expected to print DmenuItem JSON objects, one per line.
 pub async fn load_from_providers(
     provider_names: &[String],
     providers: &HashMap<String, ProviderConfig>,
+) -> Vec<LoadedItem> {
+    load_providers(provider_names, providers, None).await
+}
+
+/// Like `load_from_providers`, but writes `query` to each source command's stdin.
+/// Used for `trigger = "on_query"` providers.
+pub async fn load_from_providers_with_query(
+    provider_names: &[String],
+    providers: &HashMap<String, ProviderConfig>,
+    query: &str,
+) -> Vec<LoadedItem> {
+    load_providers(provider_names, providers, Some(query)).await
+}
+
+async fn load_providers(
+    provider_names: &[String],
+    providers: &HashMap<String, ProviderConfig>,
+    query: Option<&str>,
 ) -> Vec<LoadedItem> {
     let mut set = tokio::task::JoinSet::new();
 
-    for name in provider_names {
+    for (position, name) in provider_names.iter().enumerate() {
         let name = name.clone();
-        let source = match providers.get(&name) {
-            Some(p) => p.source.clone(),
+        let provider = match providers.get(&name) {
+            Some(p) => p.clone(),
             None => {
                 tracing::warn!("Provider '{}' not found in config", name);
                 continue;
             }
         };
+        let query = query.map(str::to_string);
         set.spawn(async move {
-            let items = load_single_source(&source).await;
-            (name, items)
+            let items = load_provider_sources(&provider, query.as_deref()).await;
+            (position, name, provider, items)
         });
     }
 
-    let mut all_items = Vec::new();
-    while let Some(Ok((provider_name, items))) = set.join_next().await {
-        for (dmenu_item, icon) in items {
-            let source_item = SourceItem {
-                id: None,
-                title: dmenu_item.title.clone(),
-                subtitle: dmenu_item.subtitle.clone(),
-                exec_path: dmenu_item.get_field("data"),
-                source_name: provider_name.clone(),
-                icon,
-            };
-            all_items.push(LoadedItem {
-                item: source_item,
-                provider_name: provider_name.clone(),
-                dmenu_item,
-            });
-        }
+    // Collect per-provider buckets, then concatenate in config order so the
+    // list doesn't depend on which command finished first
+    let mut buckets = Vec::new();
+    while let Some(Ok(bucket)) = set.join_next().await {
+        buckets.push(bucket);
     }
+    buckets.sort_by_key(|(position, ..)| *position);
 
-    all_items
+    buckets
+        .into_iter()
+        .flat_map(|(_, provider_name, provider, items)| {
+            to_loaded_items(&provider_name, &provider, items)
+        })
+        .collect()
 }
 
-/// Spawn a single source command and parse its JSONL output.
-async fn load_single_source(source: &[String]) -> Vec<(DmenuItem, Option<IconData>)> {
-    let result = tokio::time::timeout(
-        std::time::Duration::from_secs(2),
-        spawn_and_read(source),
-    )
-    .await;
+/// Run all source commands of a provider in parallel and concatenate their items in
+/// config order. With multiple sources, items are deduplicated by the provider's `field`.
+/// The result is truncated to the provider's `max_items`.
+async fn load_provider_sources(provider: &ProviderConfig, query: Option<&str>) -> Vec<DmenuItem> {
+    let mut items = load_all_sources(provider, query).await;
+    if let Some(max) = provider.max_items {
+        items.truncate(max);
+    }
+    items
+}
 
-    match result {
-        Ok(items) => items,
-        Err(_) => {
-            tracing::warn!("Source command {:?} timed out after 2s", source);
-            Vec::new()
-        }
+async fn load_all_sources(provider: &ProviderConfig, query: Option<&str>) -> Vec<DmenuItem> {
+    let commands = provider.source_commands();
+    if let [source] = commands.as_slice() {
+        return load_single_source(source, provider, query).await;
     }
+
+    let mut set = tokio::task::JoinSet::new();
+    for (index, source) in commands.into_iter().enumerate() {
+        let source = source.to_vec();
+        let provider = provider.clone();
+        let query = query.map(str::to_string);
+        set.spawn(async move {
+            let items = load_single_source(&source, &provider, query.as_deref()).await;
+            (index, items)
+        });
+    }
+
+    let mut results = Vec::new();
+    while let Some(Ok(result)) = set.join_next().await {
+        results.push(result);
+    }
+    results.sort_by_key(|(index, _)| *index);
+
+    let mut seen = HashSet::new();
+    results
+        .into_iter()
+        .flat_map(|(_, items)| items)
+        .filter(|dmenu_item| seen.insert(provider.field_value(dmenu_item)))
+        .collect()
+}
+
+/// Rebuild loaded items from a provider's disk cache. Icons are loaded afterwards
+/// (see `icon_stream`).
+pub async fn restore_cached(
+    provider_name: String,
+    provider: ProviderConfig,
+    dmenu_items: Vec<DmenuItem>,
+) -> Vec<LoadedItem> {
+    to_loaded_items(&provider_name, &provider, dmenu_items)
 }
 
-async fn spawn_and_read(source: &[String]) -> Vec<(DmenuItem, Option<IconData>)> {
+/// Save freshly loaded items to the provider's disk cache. Cloning, serializing and
+/// writing a large list is slow, so it all happens on a blocking thread.
+pub async fn write_cache(provider_name: String, items: &[LoadedItem]) {
+    let dmenu_items: Vec<DmenuItem> = items.iter().map(|li| li.dmenu_item.clone()).collect();
+    let write = move || heats_core::cache::write(&provider_name, dmenu_items);
+    if let Err(e) = tokio::task::spawn_blocking(write).await {
+        tracing::warn!("Cache write task failed: {}", e);
+    }
+}
+
+/// Build display items, applying the provider's title/subtitle templates.
+/// Items start without an icon; `icon_stream` loads them once the list is shown.
+fn to_loaded_items(
+    provider_name: &str,
+    provider: &ProviderConfig,
+    items: Vec<DmenuItem>,
+) -> Vec<LoadedItem> {
+    items
+        .into_iter()
+        .map(|dmenu_item| LoadedItem {
+            item: SourceItem {
+                id: Some(NEXT_ITEM_ID.fetch_add(1, Ordering::Relaxed)),
+                title: match &provider.title_template {
+                    Some(template) => dmenu_item.render(template),
+                    None => dmenu_item.title.clone(),
+                },
+                subtitle: match &provider.subtitle_template {
+                    Some(template) => Some(dmenu_item.render(template)),
+                    None => dmenu_item.subtitle.clone(),
+                },
+                exec_path: dmenu_item.get_field("data"),
+                source_name: provider_name.to_string(),
+                icon: None,
+            },
+            provider_name: provider_name.to_string(),
+            dmenu_item,
+        })
+        .collect()
+}
+
+/// Spawn a single source command of `provider` and parse its JSONL output.
+/// An attempt that fails (spawn error, nonzero exit or timeout) without producing any
+/// items is retried up to `provider.retries` times, each with its own timeout.
+async fn load_single_source(
+    source: &[String],
+    provider: &ProviderConfig,
+    query: Option<&str>,
+) -> Vec<DmenuItem> {
     if source.is_empty() {
         tracing::warn!("Empty source command");
         return Vec::new();
     }
 
-    // Resolve command: if not an absolute path, look next to our own executable first
-    let program = resolve_command(&source[0]);
+    for attempt in 0..=provider.retries {
+        if attempt > 0 {
+            tokio::time::sleep(std::time::Duration::from_millis(200 * u64::from(attempt))).await;
+            tracing::info!(
+                "Retrying source command {:?} ({attempt}/{})",
+                source,
+                provider.retries
+            );
+        }
 
-    let child = Command::new(&program)
-        .args(&source[1..])
-        .stdout(Stdio::piped())
-        .stderr(Stdio::null())
-        .spawn();
+        let Some(mut child) = spawn_source(source, provider.shell, &provider.env, query).await
+        else {
+            continue;
+        };
+
+        let result = tokio::time::timeout(
+            std::time::Duration::from_secs(2),
+            read_source(&mut child, source),
+        )
+        .await;
+
+        match result {
+            Ok((items, succeeded)) if succeeded || !items.is_empty() => return items,
+            Ok(_) => tracing::warn!("Source command {:?} failed without output", source),
+            Err(_) => {
+                tracing::warn!("Source command {:?} timed out after 2s", source);
+                // Don't leave a hung provider running (and unreaped) after every open.
+                // Kill its whole process group: with `shell = true` the pipeline's
+                // commands would otherwise outlive `sh` and keep stdout open.
+                if let Some(pid) = child.id() {
+                    unsafe { libc::killpg(pid as i32, libc::SIGKILL) };
+                }
+                let _ = child.kill().await;
+            }
+        }
+    }
+    Vec::new()
+}
+
+/// Spawn a source command, writing `query` (on_query providers) to its stdin.
+async fn spawn_source(
+    source: &[String],
+    shell: bool,
+    env: &HashMap<String, String>,
+    query: Option<&str>,
+) -> Option<Child> {
+    let mut cmd = source_command(source, shell, env);
+    cmd.stdout(Stdio::piped()).stderr(Stdio::null());
+    // Own process group, so a timeout can kill everything the source started
+    cmd.process_group(0);
+    if query.is_some() {
+        cmd.stdin(Stdio::piped());
+    }
+    let child = cmd.spawn();
 
     let mut child = match child {
         Ok(c) => c,
         Err(e) => {
             tracing::warn!("Failed to spawn source command {:?}: {}", source, e);
-            return Vec::new();
+            return None;
         }
     };
 
+    if let (Some(query), Some(mut stdin)) = (query, child.stdin.take()) {
+        let _ = stdin.write_all(query.as_bytes()).await;
+        let _ = stdin.write_all(b"\n").await;
+        drop(stdin);
+    }
+    Some(child)
+}
+
+/// Read a source's items (JSONL or a JSON array) and wait for it to exit.
+/// Returns the items and whether the command exited successfully.
+async fn read_source(child: &mut Child, source: &[String]) -> (Vec<DmenuItem>, bool) {
     let stdout = match child.stdout.take() {
         Some(s) => s,
-        None => return Vec::new(),
+        None => return (Vec::new(), false),
     };
 
-    let reader = BufReader::new(stdout);
-    let mut lines = reader.lines();
+    let mut reader = BufReader::new(stdout);
     let mut dmenu_items = Vec::new();
 
-    while let Ok(Some(line)) = lines.next_line().await {
-        if line.trim().is_empty() {
-            continue;
+    if starts_with_array(&mut reader).await {
+        // A single JSON array (e.g. from jq or other CLIs): buffer everything
+        let mut output = String::new();
+        if let Err(e) = reader.read_to_string(&mut output).await {
+            tracing::debug!("Failed to read JSON array from {:?}: {}", source, e);
         }
-        match serde_json::from_str::<DmenuItem>(&line) {
-            Ok(dmenu_item) => {
-                dmenu_items.push(dmenu_item);
-            }
+        match serde_json::from_str::<Vec<DmenuItem>>(&output) {
+            Ok(items) => dmenu_items = items,
             Err(e) => {
-                tracing::debug!("Failed to parse JSONL line from {:?}: {}", source, e);
+                tracing::debug!("Failed to parse JSON array from {:?}: {}", source, e);
+            }
+        }
+    } else {
+        let mut lines = reader.lines();
+        while let Ok(Some(line)) = lines.next_line().await {
+            if line.trim().is_empty() {
+                continue;
+            }
+            match serde_json::from_str::<DmenuItem>(&line) {
+                Ok(dmenu_item) => {
+                    dmenu_items.push(dmenu_item);
+                }
+                Err(e) => {
+                    tracing::debug!("Failed to parse JSONL line from {:?}: {}", source, e);
+                }
             }
         }
     }
 
     // Wait for the process to exit
-    let _ = child.wait().await;
-
-    // Load icons in a blocking thread to avoid blocking the async runtime
-    tokio::task::spawn_blocking(move || {
-        dmenu_items
-            .into_iter()
-            .map(|dmenu_item| {
-                let icon = dmenu_item
-                    .icon_path
-                    .as_ref()
-                    .and_then(|p| icon::load_app_icon(&PathBuf::from(p)));
-                (dmenu_item, icon)
-            })
-            .collect()
+    let succeeded = match child.wait().await {
+        Ok(status) => status.success(),
+        Err(_) => false,
+    };
+
+    (dmenu_items, succeeded)
+}
+
+/// Skip leading whitespace and report whether the output starts with `[`.
+async fn starts_with_array<R: AsyncBufRead + Unpin>(reader: &mut R) -> bool {
+    loop {
+        let buf = match reader.fill_buf().await {
+            Ok(buf) if !buf.is_empty() => buf,
+            _ => return false,
+        };
+        match buf.iter().position(|b| !b.is_ascii_whitespace()) {
+            Some(i) => {
+                let is_array = buf[i] == b'[';
+                reader.consume(i);
+                return is_array;
+            }
+            None => {
+                let len = buf.len();
+                reader.consume(len);
+            }
+        }
+    }
+}
+
+/// Load the icons of `items` one by one in blocking threads, yielding
+/// `(item id, icon)` as each is decoded. Items without an `icon_path` are skipped.
+/// `retina` picks high-resolution variants (see `icon::load_icon_cached`).
+pub fn icon_stream(
+    items: &[LoadedItem],
+    retina: bool,
+) -> impl iced::futures::Stream<Item = (usize, IconData)> {
+    let requests: Vec<(usize, PathBuf)> = items
+        .iter()
+        .filter_map(|li| {
+            let path = li.dmenu_item.icon_path.as_ref()?;
+            Some((li.item.id?, PathBuf::from(path)))
+        })
+        .collect();
+
+    iced::stream::channel(
+        32,
+        |mut sender: iced::futures::channel::mpsc::Sender<(usize, IconData)>| async move {
+            for (id, path) in requests {
+                let load = move || icon::load_icon_cached(&path, retina);
+                let icon = tokio::task::spawn_blocking(load).await;
+                if let Ok(Some(icon)) = icon {
+                    if sender.send((id, icon)).await.is_err() {
+                        break;
+                    }
+                }
+            }
+        },
+    )
+}
+
+/// Largest preview output kept (in bytes); the rest is cut off
+const PREVIEW_MAX_BYTES: usize = 64 * 1024;
+
+/// Run a provider's `preview` command with the item's field values appended and return
+/// its stdout (lossy UTF-8). The process is killed when it outlives the timeout or the
+/// future is dropped (a newer selection aborted this preview).
+pub async fn run_preview(
+    preview: &[String],
+    args: &[String],
+    env: &HashMap<String, String>,
+) -> String {
+    let mut cmd = source_command(preview, false, env);
+    cmd.args(args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .kill_on_drop(true);
+
+    let child = match cmd.spawn() {
+        Ok(child) => child,
+        Err(e) => {
+            tracing::warn!("Failed to spawn preview {:?}: {}", preview, e);
+            return format!("Failed to run {}: {e}", preview[0]);
+        }
+    };
+
+    let timeout = std::time::Duration::from_secs(2);
+    match tokio::time::timeout(timeout, child.wait_with_output()).await {
+        Ok(Ok(output)) => {
+            let stdout = &output.stdout[..output.stdout.len().min(PREVIEW_MAX_BYTES)];
+            String::from_utf8_lossy(stdout).into_owned()
+        }
+        Ok(Err(e)) => {
+            tracing::warn!("Failed to read preview {:?}: {}", preview, e);
+            String::new()
+        }
+        Err(_) => {
+            tracing::warn!("Preview command {:?} timed out after 2s", preview);
+            "Preview timed out".to_string()
+        }
+    }
+}
+
+/// Run a history update (recent apps, evaluator history, clipboard) on a blocking
+/// thread, so reading and rewriting its file never stalls the UI.
+pub fn record_in_background<T: Send + 'static>(
+    record: impl FnOnce() + Send + 'static,
+) -> iced::Task<T> {
+    iced::Task::future(async move {
+        if let Err(e) = tokio::task::spawn_blocking(record).await {
+            tracing::warn!("History write task failed: {}", e);
+        }
     })
-    .await
-    .unwrap_or_default()
+    .discard()
 }
 
-/// Execute an action by running the provider's action command with the field value from the DmenuItem.
-pub fn execute_action(provider: &ProviderConfig, dmenu_item: &DmenuItem) {
-    let field_value = dmenu_item.get_field(&provider.field);
+/// Execute an action by running the provider's action command with the field values from the DmenuItem.
+/// The returned task records a launched app for heats-list-recent.
+pub fn execute_action<T: Send + 'static>(
+    provider: &ProviderConfig,
+    dmenu_item: &DmenuItem,
+) -> iced::Task<T> {
+    let field_values = provider.field_values(dmenu_item);
 
     if provider.action.is_empty() {
         tracing::error!("Provider action command is empty");
-        return;
+        return iced::Task::none();
     }
 
     let program = resolve_command(&provider.action[0]);
-    let mut args: Vec<&str> = provider.action[1..].iter().map(|s| s.as_str()).collect();
-    args.push(&field_value);
+    let args = provider
+        .action_placement
+        .arguments(&provider.action[1..], &field_values);
 
     tracing::info!("Executing action: {} {:?}", program, args);
 
     match std::process::Command::new(&program)
         .args(&args)
+        .envs(expand_env(&provider.env))
         .stdin(Stdio::null())
         .stdout(Stdio::null())
         .stderr(Stdio::null())
         .spawn()
     {
-        Ok(_) => {}
+        Ok(_) => {
+            if provider.notify {
+                notify_action(dmenu_item);
+            }
+            // Launched app bundles feed heats-list-recent
+            if let [app_path] = field_values.as_slice() {
+                let app_path = app_path.trim_end_matches('/').to_string();
+                if app_path.ends_with(".app") {
+                    return record_in_background(move || heats_core::recent::record(&app_path));
+                }
+            }
+            iced::Task::none()
+        }
         Err(e) => {
             tracing::error!("Failed to execute action '{}': {}", &program, e);
+            iced::Task::none()
         }
     }
 }
@@ -188,11 +476,12 @@ pub fn run_action(config: &EvaluatorConfig, dmenu_item: &DmenuItem) {
 
     let program = resolve_command(&config.action[0]);
 
-    match config.action_input {
+    let spawned = match config.action_input {
         InputMode::Stdin => {
             tracing::info!("Executing evaluator action (stdin): {} {:?}", program, &config.action[1..]);
             let child = std::process::Command::new(&program)
                 .args(&config.action[1..])
+                .envs(expand_env(&config.env))
                 .stdin(Stdio::piped())
                 .stdout(Stdio::null())
                 .stderr(Stdio::null())
@@ -205,29 +494,174 @@ pub fn run_action(config: &EvaluatorConfig, dmenu_item: &DmenuItem) {
                         drop(stdin); // close stdin so the process can finish
                     }
                     let _ = c.wait(); // reap the child to avoid zombies
+                    true
                 }
                 Err(e) => {
                     tracing::error!("Failed to execute evaluator action '{}': {}", &program, e);
+                    false
                 }
             }
         }
         InputMode::Arg => {
-            let mut args: Vec<&str> = config.action[1..].iter().map(|s| s.as_str()).collect();
-            args.push(&field_value);
+            let args = config
+                .action_placement
+                .arguments(&config.action[1..], std::slice::from_ref(&field_value));
             tracing::info!("Executing evaluator action (arg): {} {:?}", program, args);
             match std::process::Command::new(&program)
                 .args(&args)
+                .envs(expand_env(&config.env))
                 .stdin(Stdio::null())
                 .stdout(Stdio::null())
                 .stderr(Stdio::null())
                 .spawn()
             {
-                Ok(_) => {}
+                Ok(_) => true,
                 Err(e) => {
                     tracing::error!("Failed to execute evaluator action '{}': {}", &program, e);
+                    false
                 }
             }
         }
+    };
+
+    if spawned && config.notify {
+        notify_action(dmenu_item);
+    }
+}
+
+/// Confirm a finished action with a notification showing the item's title.
+fn notify_action(dmenu_item: &DmenuItem) {
+    heats_core::platform::macos::post_notification("Heats", &dmenu_item.title);
+}
+
+/// Spawn a show/hide hook command without waiting for it to finish.
+pub fn spawn_hook(hook: &[String]) {
+    if hook.is_empty() {
+        return;
+    }
+
+    let program = resolve_command(&hook[0]);
+    tracing::debug!("Running hook: {} {:?}", program, &hook[1..]);
+
+    if let Err(e) = std::process::Command::new(&program)
+        .args(&hook[1..])
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+    {
+        tracing::warn!("Failed to run hook '{}': {}", &program, e);
+    }
+}
+
+/// Build the command for a source. With `shell`, the words are joined and run via
+/// `sh -c` (so pipelines and globs work); extra arguments become `$1`, `$2`, ...
+/// Otherwise the first word is resolved with `resolve_command` and run as argv.
+/// `env` is added to the command's environment (see `expand_env`).
+pub fn source_command(source: &[String], shell: bool, env: &HashMap<String, String>) -> Command {
+    let mut cmd = if shell {
+        let mut cmd = Command::new("sh");
+        cmd.arg("-c").arg(source.join(" ")).arg("sh");
+        cmd
+    } else {
+        // Resolve command: if not an absolute path, look next to our own executable first
+        let mut cmd = Command::new(resolve_command(&source[0]));
+        cmd.args(&source[1..]);
+        cmd
+    };
+    cmd.envs(expand_env(env));
+    cmd
+}
+
+/// A provider/evaluator `env` table with `$VAR` and `${VAR}` in the values replaced
+/// from heatsd's own environment. Unset variables expand to an empty string.
+pub fn expand_env(env: &HashMap<String, String>) -> Vec<(&str, String)> {
+    env.iter()
+        .map(|(key, value)| (key.as_str(), expand_vars(value)))
+        .collect()
+}
+
+fn expand_vars(value: &str) -> String {
+    let mut expanded = String::new();
+    let mut rest = value;
+    while let Some(dollar) = rest.find('$') {
+        expanded.push_str(&rest[..dollar]);
+        rest = &rest[dollar + 1..];
+        let (name, after) = match rest.strip_prefix('{') {
+            Some(braced) => match braced.find('}') {
+                Some(end) => (&braced[..end], &braced[end + 1..]),
+                None => ("", rest),
+            },
+            None => {
+                let end = rest
+                    .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                    .unwrap_or(rest.len());
+                (&rest[..end], &rest[end..])
+            }
+        };
+        if name.is_empty() {
+            // Not a variable reference: keep the `$` as is
+            expanded.push('$');
+            continue;
+        }
+        expanded.push_str(&std::env::var(name).unwrap_or_default());
+        rest = after;
+    }
+    expanded.push_str(rest);
+    expanded
+}
+
+/// Extend PATH for spawned commands. launchd starts heatsd with a minimal PATH, so
+/// the login shell's PATH (e.g. with /opt/homebrew/bin) goes first, then our own,
+/// then `[env] path`. Must run before other threads are started.
+pub fn init_path(env: &EnvConfig) {
+    let current = std::env::var_os("PATH").unwrap_or_default();
+    let login = login_shell_path().unwrap_or_default();
+    let mut dirs: Vec<PathBuf> = Vec::new();
+    let candidates = std::env::split_paths(&login)
+        .chain(std::env::split_paths(&current))
+        .chain(env.path_dirs());
+    for dir in candidates {
+        if !dir.as_os_str().is_empty() && !dirs.contains(&dir) {
+            dirs.push(dir);
+        }
+    }
+    match std::env::join_paths(&dirs) {
+        Ok(path) => {
+            tracing::debug!("PATH={}", path.to_string_lossy());
+            std::env::set_var("PATH", path);
+        }
+        Err(e) => tracing::warn!("Failed to extend PATH: {e}"),
+    }
+}
+
+/// PATH as set up by the user's login shell, or None if it can't be determined in time.
+fn login_shell_path() -> Option<String> {
+    let shell = std::env::var("SHELL").unwrap_or_else(|_| "/bin/zsh".to_string());
+    let (tx, rx) = std::sync::mpsc::channel();
+    // A slow or interactive shell profile must not hold up startup
+    std::thread::spawn(move || {
+        let output = std::process::Command::new(&shell)
+            .args(["-l", "-c", "printf %s \"$PATH\""])
+            .stdin(Stdio::null())
+            .stderr(Stdio::null())
+            .output();
+        let _ = tx.send(output);
+    });
+    match rx.recv_timeout(std::time::Duration::from_secs(3)) {
+        Ok(Ok(output)) if output.status.success() => String::from_utf8(output.stdout).ok(),
+        Ok(Ok(output)) => {
+            tracing::warn!("Login shell exited with {}", output.status);
+            None
+        }
+        Ok(Err(e)) => {
+            tracing::warn!("Failed to run login shell: {e}");
+            None
+        }
+        Err(_) => {
+            tracing::warn!("Login shell timed out, keeping the inherited PATH");
+            None
+        }
     }
 }
 
@@ -1,13 +1,53 @@
 use std::collections::HashMap;
 use std::process::Stdio;
+use std::time::Duration;
 
 use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
-use tokio::process::Command;
 
-use crate::command::{resolve_command, LoadedItem};
+use crate::command::{source_command, LoadedItem};
 use heats_core::config::{EvaluatorConfig, InputMode};
 use heats_core::source::{DmenuItem, SourceItem};
 
+/// The query an evaluator receives: with its `prefix` stripped,
+/// or None if the evaluator has a prefix the query doesn't start with.
+fn evaluator_query<'a>(query: &'a str, config: &EvaluatorConfig) -> Option<&'a str> {
+    match &config.prefix {
+        Some(prefix) => query.strip_prefix(prefix.as_str()).map(str::trim_start),
+        None => Some(query),
+    }
+}
+
+/// Filter evaluators to those whose `prefix` and `min_chars` the query satisfies.
+pub fn eligible_evaluators(
+    query: &str,
+    evaluator_names: &[String],
+    configs: &HashMap<String, EvaluatorConfig>,
+) -> Vec<String> {
+    evaluator_names
+        .iter()
+        .filter(|name| {
+            configs.get(*name).is_none_or(|c| {
+                evaluator_query(query, c).is_some_and(|q| q.chars().count() >= c.min_chars)
+            })
+        })
+        .cloned()
+        .collect()
+}
+
+/// Debounce before running the given evaluators: the longest `debounce_ms` among them.
+pub fn debounce(
+    evaluator_names: &[String],
+    configs: &HashMap<String, EvaluatorConfig>,
+) -> Duration {
+    let ms = evaluator_names
+        .iter()
+        .filter_map(|name| configs.get(name))
+        .map(|c| c.debounce_ms)
+        .max()
+        .unwrap_or(0);
+    Duration::from_millis(ms)
+}
+
 /// Run all evaluators for the given query and return results.
 pub async fn run_evaluators(
     query: &str,
@@ -30,7 +70,10 @@ pub async fn run_evaluators(
                 continue;
             }
         };
-        let query = query.to_string();
+        let query = match evaluator_query(query, &config) {
+            Some(q) => q.to_string(),
+            None => continue,
+        };
         set.spawn(async move {
             let items = run_single_evaluator(&query, &config).await;
             (name, items)
@@ -73,9 +116,7 @@ async fn run_single_evaluator(query: &str, config: &EvaluatorConfig) -> Vec<Dmen
         return Vec::new();
     }
 
-    let program = resolve_command(&config.source[0]);
-    let mut cmd = Command::new(&program);
-    cmd.args(&config.source[1..]);
+    let mut cmd = source_command(&config.source, config.shell, &config.env);
 
     match config.input {
         InputMode::Stdin => {
@@ -1,15 +1,161 @@
-use std::path::Path;
-use std::sync::Arc;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
 
 use icns::{IconFamily, IconType, PixelFormat};
 
 use heats_core::source::IconData;
 
-/// Load an app icon from a .app bundle as 32x32 RGBA pixel data.
+/// Maximum number of decoded icons kept in memory
+const ICON_CACHE_CAPACITY: usize = 512;
+
+/// Largest icon edge accepted from an .icns file (larger variants are skipped)
+const MAX_ICON_DIMENSION: u32 = 1024;
+/// Largest .icns file read into memory
+const MAX_ICNS_FILE_SIZE: u64 = 32 * 1024 * 1024;
+
+/// Pixel size image icons are scaled to (the list shows icons at 24pt)
+const ICON_SIZE: u32 = 32;
+
+/// A decoded icon (or a failed decode), keyed by `icon_path`, modification time
+/// and whether the Retina variant was requested
+struct CachedIcon {
+    icon_path: PathBuf,
+    modified: Option<SystemTime>,
+    retina: bool,
+    icon: Option<IconData>,
+}
+
+/// Decoded icons shared by all providers, least recently used first
+static ICON_CACHE: Mutex<Vec<CachedIcon>> = Mutex::new(Vec::new());
+
+/// Like `load_icon`, but decodes each icon once per daemon lifetime.
+/// An entry is reused while the file's mtime is unchanged (app updates re-decode).
+pub fn load_icon_cached(icon_path: &Path, retina: bool) -> Option<IconData> {
+    let modified = std::fs::metadata(icon_path).and_then(|m| m.modified()).ok();
+
+    {
+        let mut cache = ICON_CACHE.lock().unwrap();
+        if let Some(pos) = cache.iter().position(|e| e.icon_path == icon_path) {
+            let entry = cache.remove(pos);
+            if entry.modified == modified && entry.retina == retina {
+                let icon = entry.icon.clone();
+                cache.push(entry);
+                return icon;
+            }
+        }
+    }
+
+    // Decode outside the lock so other icons can be served meanwhile
+    let icon = load_icon(icon_path, retina);
+
+    let mut cache = ICON_CACHE.lock().unwrap();
+    cache.retain(|e| e.icon_path != icon_path);
+    if cache.len() >= ICON_CACHE_CAPACITY {
+        cache.remove(0);
+    }
+    cache.push(CachedIcon {
+        icon_path: icon_path.to_path_buf(),
+        modified,
+        retina,
+        icon: icon.clone(),
+    });
+    icon
+}
+
+/// Load an item's `icon_path`: a PNG/JPEG/SVG image file, or else a .app bundle.
+fn load_icon(icon_path: &Path, retina: bool) -> Option<IconData> {
+    let extension = icon_path
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(str::to_ascii_lowercase);
+    match extension.as_deref() {
+        Some("png" | "jpg" | "jpeg") => load_image_rgba(icon_path, retina),
+        Some("svg") => load_svg_rgba(icon_path, retina),
+        _ => load_app_icon(icon_path, retina),
+    }
+}
+
+/// Pixel size image and SVG icons are rendered at.
+fn icon_size(retina: bool) -> u32 {
+    if retina {
+        ICON_SIZE * 2
+    } else {
+        ICON_SIZE
+    }
+}
+
+/// Decode a PNG/JPEG file and scale it down to the icon size (kept as is if smaller).
+fn load_image_rgba(path: &Path, retina: bool) -> Option<IconData> {
+    let decoded = match image::open(path) {
+        Ok(decoded) => decoded,
+        Err(e) => {
+            tracing::debug!("Failed to decode icon {}: {}", path.display(), e);
+            return None;
+        }
+    };
+
+    let size = icon_size(retina);
+    let scaled = if decoded.width() > size || decoded.height() > size {
+        decoded.thumbnail(size, size)
+    } else {
+        decoded
+    };
+    let rgba = scaled.into_rgba8();
+
+    Some(IconData::Rgba {
+        width: rgba.width(),
+        height: rgba.height(),
+        pixels: Arc::new(rgba.into_raw()),
+    })
+}
+
+/// Rasterize an SVG file to the icon size, keeping its aspect ratio.
+fn load_svg_rgba(path: &Path, retina: bool) -> Option<IconData> {
+    use resvg::{tiny_skia, usvg};
+
+    let data = std::fs::read(path).ok()?;
+    let tree = match usvg::Tree::from_data(&data, &usvg::Options::default()) {
+        Ok(tree) => tree,
+        Err(e) => {
+            tracing::debug!("Failed to parse SVG icon {}: {}", path.display(), e);
+            return None;
+        }
+    };
+
+    let size = icon_size(retina) as f32;
+    let svg_size = tree.size();
+    let scale = size / svg_size.width().max(svg_size.height());
+    let width = (svg_size.width() * scale).round().max(1.0) as u32;
+    let height = (svg_size.height() * scale).round().max(1.0) as u32;
+
+    let mut pixmap = tiny_skia::Pixmap::new(width, height)?;
+    let transform = tiny_skia::Transform::from_scale(scale, scale);
+    resvg::render(&tree, transform, &mut pixmap.as_mut());
+
+    // tiny-skia renders premultiplied alpha; the image widget expects straight RGBA
+    let pixels = pixmap
+        .pixels()
+        .iter()
+        .flat_map(|p| {
+            let c = p.demultiply();
+            [c.red(), c.green(), c.blue(), c.alpha()]
+        })
+        .collect();
+
+    Some(IconData::Rgba {
+        width,
+        height,
+        pixels: Arc::new(pixels),
+    })
+}
+
+/// Load an app icon from a .app bundle as RGBA pixel data: 32x32, or 64x64 when
+/// `retina` (the display's backing scale factor is 2 or more).
 ///
 /// Reads `Contents/Info.plist` → `CFBundleIconFile` → `Contents/Resources/{icon}.icns`,
-/// then extracts 32x32 RGBA pixels. Returns `None` on any failure.
-pub fn load_app_icon(app_path: &Path) -> Option<IconData> {
+/// then extracts RGBA pixels. Returns `None` on any failure.
+fn load_app_icon(app_path: &Path, retina: bool) -> Option<IconData> {
     let icon_file = icon_file_from_plist(app_path)?;
     let icns_path = if icon_file.ends_with(".icns") {
         app_path.join("Contents/Resources").join(&icon_file)
@@ -19,7 +165,7 @@ pub fn load_app_icon(app_path: &Path) -> Option<IconData> {
             .join(format!("{icon_file}.icns"))
     };
 
-    load_icns_rgba(&icns_path)
+    load_icns_rgba(&icns_path, retina)
 }
 
 /// Read `CFBundleIconFile` from the app's Info.plist.
@@ -34,10 +180,66 @@ fn icon_file_from_plist(app_path: &Path) -> Option<String> {
 }
 
 /// Load an .icns file and extract RGBA pixel data.
+/// Oversized, malformed or undecodable files yield `None` (logged at debug level)
+/// instead of large allocations or a panic in the `icns` crate.
+fn load_icns_rgba(icns_path: &Path, retina: bool) -> Option<IconData> {
+    let size = std::fs::metadata(icns_path).ok()?.len();
+    if size > MAX_ICNS_FILE_SIZE {
+        tracing::debug!(
+            "Skipping icon {}: file is {} bytes",
+            icns_path.display(),
+            size
+        );
+        return None;
+    }
+    let data = std::fs::read(icns_path).ok()?;
+    if !icns_layout_is_valid(&data) {
+        tracing::debug!(
+            "Skipping icon {}: malformed icns layout",
+            icns_path.display()
+        );
+        return None;
+    }
+
+    match std::panic::catch_unwind(|| decode_icns(&data, retina)) {
+        Ok(Ok(icon)) => icon,
+        Ok(Err(e)) => {
+            tracing::debug!("Failed to decode icon {}: {}", icns_path.display(), e);
+            None
+        }
+        Err(_) => {
+            tracing::debug!("Panic while decoding icon {}", icns_path.display());
+            None
+        }
+    }
+}
+
+/// Check that the icns header and every element length stay within the file, so
+/// the `icns` crate never allocates for a length the file doesn't contain.
+fn icns_layout_is_valid(data: &[u8]) -> bool {
+    let read_u32 = |offset: usize| {
+        data.get(offset..offset + 4)
+            .map(|b| u32::from_be_bytes([b[0], b[1], b[2], b[3]]) as usize)
+    };
+
+    if data.get(0..4) != Some(b"icns".as_slice()) || read_u32(4) != Some(data.len()) {
+        return false;
+    }
+    let mut offset = 8;
+    while offset < data.len() {
+        match read_u32(offset + 4) {
+            Some(length) if (8..=data.len() - offset).contains(&length) => offset += length,
+            _ => return false,
+        }
+    }
+    true
+}
+
+/// Extract RGBA pixel data from icns file contents.
 /// Tries multiple sizes from small to large; the image widget scales to 24px display.
-fn load_icns_rgba(icns_path: &Path) -> Option<IconData> {
-    let file = std::io::BufReader::new(std::fs::File::open(icns_path).ok()?);
-    let icon_family = IconFamily::read(file).ok()?;
+/// With `retina`, sizes below 64x64 are skipped so the 24pt icon (48px) is downscaled.
+fn decode_icns(data: &[u8], retina: bool) -> std::io::Result<Option<IconData>> {
+    let icon_family = IconFamily::read(data)?;
 
     // Prefer smaller sizes first (less memory), fall back to larger ones.
     // Modern apps often only ship 128x128+ or retina variants.
@@ -54,19 +256,26 @@ fn load_icns_rgba(icns_path: &Path) -> Option<IconData> {
         IconType::RGBA32_512x512,
         IconType::RGBA32_512x512_2x,
     ];
+    let min_width = if retina { 64 } else { 0 };
 
     for icon_type in types_to_try {
+        if icon_type.pixel_width() < min_width || icon_type.pixel_width() > MAX_ICON_DIMENSION {
+            continue;
+        }
         if let Ok(image) = icon_family.get_icon_with_type(icon_type) {
             let rgba = image.convert_to(PixelFormat::RGBA);
             let w = rgba.width();
             let h = rgba.height();
-            return Some(IconData::Rgba {
+            if w > MAX_ICON_DIMENSION || h > MAX_ICON_DIMENSION {
+                continue;
+            }
+            return Ok(Some(IconData::Rgba {
                 width: w,
                 height: h,
                 pixels: Arc::new(rgba.into_data().into_vec()),
-            });
+            }));
         }
     }
 
-    None
+    Ok(None)
 }
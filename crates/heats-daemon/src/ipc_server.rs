@@ -1,31 +1,30 @@
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
 use iced::futures::SinkExt;
 use iced::Subscription;
-use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
-use tokio::net::UnixListener;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader, Interest};
+use tokio::net::{UnixListener, UnixStream};
 use tokio::sync::oneshot;
 
-use crate::app::{Message, ResponseSender};
+use crate::app::{DmenuSelection, Message, ResponseSender};
+use heats_core::ipc::{IpcCommand, IpcContext, IpcReply, IpcResponse, ResponseFormat};
 use heats_core::source::{DmenuItem, SourceItem};
 
-/// IPC context sent as the first line by the client
-#[derive(serde::Deserialize)]
-struct IpcContext {
-    format: String,
-}
-
 /// Create an iced Subscription that listens on the Unix domain socket.
-/// Accepts one connection at a time: reads line-delimited items, then sends
+/// Each connection is served in its own task: it reads line-delimited items, then sends
 /// a `Message::DmenuSession` containing the items and a oneshot channel for the response.
-pub fn dmenu_subscription() -> Subscription<Message> {
-    Subscription::run(dmenu_stream)
+/// Sessions run one at a time; a client connecting during a session waits for its turn.
+/// Each session keeps at most `max_items` lines (`[dmenu] max_items`).
+pub fn dmenu_subscription(max_items: usize) -> Subscription<Message> {
+    Subscription::run_with(max_items, dmenu_stream)
 }
 
-fn dmenu_stream() -> impl iced::futures::Stream<Item = Message> {
+fn dmenu_stream(max_items: &usize) -> impl iced::futures::Stream<Item = Message> {
+    let max_items = *max_items;
     iced::stream::channel(
         4,
-        |mut sender: iced::futures::channel::mpsc::Sender<Message>| async move {
+        move |mut sender: iced::futures::channel::mpsc::Sender<Message>| async move {
             let sock_path = heats_core::ipc::socket_path();
 
             // Remove stale socket (in case daemon didn't clean up)
@@ -44,6 +43,10 @@ fn dmenu_stream() -> impl iced::futures::Stream<Item = Message> {
                 }
             };
 
+            // Serializes dmenu sessions; connections are still accepted (and their
+            // items read) while a session is open
+            let session_lock = Arc::new(tokio::sync::Mutex::new(()));
+
             loop {
                 let (stream, _addr) = match listener.accept().await {
                     Ok(conn) => conn,
@@ -53,162 +56,376 @@ fn dmenu_stream() -> impl iced::futures::Stream<Item = Message> {
                     }
                 };
 
-                tracing::debug!("IPC client connected");
+                let sender = sender.clone();
+                let session_lock = session_lock.clone();
+                tokio::spawn(handle_connection(stream, sender, session_lock, max_items));
+            }
+        },
+    )
+}
 
-                let mut reader = BufReader::new(stream);
+/// How often a queued client is checked for having disconnected
+const CLIENT_CHECK_INTERVAL: Duration = Duration::from_millis(250);
 
-                // Read the first line as context
-                let mut first_line = String::new();
-                match reader.read_line(&mut first_line).await {
-                    Ok(0) => {
-                        tracing::debug!("IPC client disconnected immediately");
-                        continue;
-                    }
-                    Ok(_) => {}
-                    Err(e) => {
-                        tracing::error!("IPC read error on context line: {}", e);
-                        continue;
-                    }
-                }
+/// Resolve once the client has closed its end of the connection (e.g. Ctrl-C while
+/// queued). Reads can't tell: the client already shut down writing after its items,
+/// so they return EOF either way. A full close shows up as a hang-up instead.
+async fn client_gone(stream: &UnixStream) {
+    loop {
+        match stream.ready(Interest::WRITABLE).await {
+            Ok(ready) if !ready.is_write_closed() => {}
+            _ => return,
+        }
+        tokio::time::sleep(CLIENT_CHECK_INTERVAL).await;
+    }
+}
 
-                let first_line = first_line.trim().to_string();
-
-                // Try to parse as IPC context
-                let (format, remaining_first_line) =
-                    match serde_json::from_str::<IpcContext>(&first_line) {
-                        Ok(ctx) => (ctx.format, None),
-                        Err(_) => {
-                            // Not a context line — treat as legacy text format
-                            // The first line is actually an item
-                            ("text".to_string(), Some(first_line))
-                        }
-                    };
-
-                let is_jsonl = format == "jsonl";
-
-                // Read remaining lines
-                let mut raw_lines = Vec::new();
-                if let Some(line) = remaining_first_line {
-                    if !line.is_empty() {
-                        raw_lines.push(line);
-                    }
-                }
+/// Serve one client connection: read its context and items, then run a dmenu session
+/// once no other session is open, and write the selection back.
+async fn handle_connection(
+    stream: UnixStream,
+    mut sender: iced::futures::channel::mpsc::Sender<Message>,
+    session_lock: Arc<tokio::sync::Mutex<()>>,
+    max_items: usize,
+) {
+    tracing::debug!("IPC client connected");
 
-                loop {
-                    let mut line = String::new();
-                    match reader.read_line(&mut line).await {
-                        Ok(0) => break, // EOF
-                        Ok(_) => {
-                            let trimmed = line.trim_end_matches('\n').trim_end_matches('\r');
-                            if !trimmed.is_empty() {
-                                raw_lines.push(trimmed.to_string());
-                            }
-                        }
-                        Err(e) => {
-                            tracing::error!("IPC read error: {}", e);
-                            break;
-                        }
-                    }
-                }
+    let mut reader = BufReader::new(stream);
 
-                if raw_lines.is_empty() {
-                    tracing::debug!("IPC client sent no items, ignoring");
-                    continue;
-                }
+    // Read the first line as context
+    let mut first_line = String::new();
+    match reader.read_line(&mut first_line).await {
+        Ok(0) => {
+            tracing::debug!("IPC client disconnected immediately");
+            return;
+        }
+        Ok(_) => {}
+        Err(e) => {
+            tracing::error!("IPC read error on context line: {}", e);
+            return;
+        }
+    }
 
-                tracing::info!(
-                    "IPC received {} items (format: {})",
-                    raw_lines.len(),
-                    format
-                );
+    let first_line = first_line.trim().to_string();
 
-                // Convert to SourceItems based on format.
-                // Each item's `id` field stores its original raw_lines index.
-                let items: Vec<SourceItem> = if is_jsonl {
-                    raw_lines
-                        .iter()
-                        .enumerate()
-                        .filter_map(|(idx, line)| {
-                            match serde_json::from_str::<DmenuItem>(line) {
-                                Ok(di) => Some(SourceItem {
-                                    id: Some(idx),
-                                    title: di.title.clone(),
-                                    subtitle: di.subtitle.clone(),
-                                    exec_path: di.get_field("data"),
-                                    source_name: "dmenu".to_string(),
-                                    icon: None,
-                                }),
-                                Err(e) => {
-                                    tracing::debug!("Failed to parse JSONL line: {}", e);
-                                    None
-                                }
-                            }
-                        })
-                        .collect()
-                } else {
-                    raw_lines
-                        .iter()
-                        .enumerate()
-                        .map(|(idx, title)| {
-                            SourceItem {
-                                id: Some(idx),
-                                title: title.clone(),
-                                subtitle: None,
-                                exec_path: String::new(),
-                                source_name: "dmenu".to_string(),
-                                icon: None,
-                            }
-                        })
-                        .collect()
-                };
+    if let Ok(command) = serde_json::from_str::<IpcCommand>(&first_line) {
+        handle_command(command, reader.into_inner(), &mut sender).await;
+        return;
+    }
+
+    // Try to parse as IPC context
+    let (context, remaining_first_line) = match serde_json::from_str::<IpcContext>(&first_line) {
+        Ok(ctx) => (ctx, None),
+        Err(_) => {
+            // Not a context line — treat as legacy text format
+            // The first line is actually an item
+            let ctx = IpcContext {
+                format: "text".to_string(),
+                ..IpcContext::default()
+            };
+            (ctx, Some(first_line))
+        }
+    };
 
-                // Create a oneshot channel for the response (selected index)
-                let (response_tx, response_rx) = oneshot::channel::<Option<usize>>();
+    let format = context.format.as_str();
+    let is_jsonl = format == "jsonl";
 
-                // Wrap sender in Arc<Mutex<Option<...>>> so Message can be Clone
-                let wrapped_tx = ResponseSender(Arc::new(Mutex::new(Some(response_tx))));
+    // Read remaining lines
+    let mut raw_lines = Vec::new();
+    if let Some(line) = remaining_first_line {
+        if !line.is_empty() {
+            raw_lines.push(line);
+        }
+    }
 
-                // Send the session to the iced app
-                let msg = Message::DmenuSession {
-                    items,
-                    response_tx: wrapped_tx,
-                };
-                if sender.send(msg).await.is_err() {
-                    tracing::error!("Failed to send DmenuSession to app");
+    // A live session opens right away; its lines are appended as they arrive.
+    // Lines past `max_items` are still read (so the client can finish writing) but dropped.
+    let mut dropped = 0usize;
+    let mut line = String::new();
+    while !context.live {
+        line.clear();
+        match reader.read_line(&mut line).await {
+            Ok(0) => break, // EOF
+            Ok(_) => {
+                let trimmed = line.trim_end_matches('\n').trim_end_matches('\r');
+                if trimmed.is_empty() {
                     continue;
                 }
+                if raw_lines.len() < max_items {
+                    raw_lines.push(trimmed.to_string());
+                } else {
+                    dropped += 1;
+                }
+            }
+            Err(e) => {
+                tracing::error!("IPC read error: {}", e);
+                break;
+            }
+        }
+    }
 
-                // Wait for the app to send back a response (item ID = raw_lines index),
-                // then write the corresponding raw line to the client
-                let stream = reader.into_inner();
-                match response_rx.await {
-                    Ok(Some(item_id)) => {
-                        let response = match raw_lines.get(item_id) {
-                            Some(line) => line.clone(),
-                            None => {
-                                tracing::warn!(
-                                    "IPC: item id {} out of range (raw_lines len={})",
-                                    item_id,
-                                    raw_lines.len()
-                                );
-                                String::new()
-                            }
-                        };
+    if raw_lines.is_empty() && !context.live {
+        tracing::debug!("IPC client sent no items, ignoring");
+        return;
+    }
 
-                        let mut writer = stream;
-                        let payload = format!("{response}\n");
-                        if let Err(e) = writer.write_all(payload.as_bytes()).await {
-                            tracing::error!("IPC write error: {}", e);
-                        }
-                        let _ = writer.shutdown().await;
+    tracing::info!(
+        "IPC received {} items (format: {})",
+        raw_lines.len(),
+        format
+    );
+    if dropped > 0 {
+        tracing::warn!(
+            "IPC: dropped {} items past the [dmenu] max_items limit of {}",
+            dropped,
+            max_items
+        );
+    }
+
+    let items = to_source_items(&raw_lines, 0, is_jsonl);
+
+    // Wait for any active session to finish (FIFO) so it isn't torn down mid-interaction
+    let _session = match session_lock.try_lock() {
+        Ok(guard) => guard,
+        Err(_) => {
+            tracing::info!("IPC: dmenu session active, queueing this one");
+            tokio::select! {
+                guard = session_lock.lock() => guard,
+                () = client_gone(reader.get_ref()) => {
+                    tracing::info!("IPC: queued client disconnected, dropping its session");
+                    return;
+                }
+            }
+        }
+    };
+
+    // Create a oneshot channel for the response (selected item IDs)
+    let (response_tx, response_rx) = oneshot::channel::<Option<DmenuSelection>>();
+
+    // Wrap sender in Arc<Mutex<Option<...>>> so Message can be Clone
+    let wrapped_tx = ResponseSender(Arc::new(Mutex::new(Some(response_tx))));
+
+    // Send the session to the iced app
+    let msg = Message::DmenuSession {
+        items,
+        context: context.clone(),
+        response_tx: wrapped_tx,
+    };
+    if sender.send(msg).await.is_err() {
+        tracing::error!("Failed to send DmenuSession to app");
+        return;
+    }
+
+    // Wait for the app to send back a response (item ID = raw_lines index),
+    // appending lines of a live session meanwhile; then write the corresponding
+    // raw line to the client
+    let timeout = async {
+        match context.timeout_ms {
+            Some(timeout_ms) => tokio::time::sleep(Duration::from_millis(timeout_ms)).await,
+            None => std::future::pending().await,
+        }
+    };
+    tokio::pin!(timeout);
+    let mut response_rx = response_rx;
+    let mut reading = context.live;
+    let response = loop {
+        tokio::select! {
+            response = &mut response_rx => break response,
+            _ = &mut timeout => {
+                tracing::info!(
+                    "IPC: dmenu session timed out after {}ms",
+                    context.timeout_ms.unwrap_or_default()
+                );
+                // Drop response_rx first so the app can tell
+                // this session (and not a newer one) has ended
+                drop(response_rx);
+                let _ = sender.send(Message::DmenuTimedOut).await;
+                break Ok(None);
+            }
+            lines = read_available_lines(&mut reader), if reading => {
+                if lines.is_empty() {
+                    // EOF: the list is complete
+                    reading = false;
+                    continue;
+                }
+                let room = max_items.saturating_sub(raw_lines.len());
+                if lines.len() > room {
+                    if dropped == 0 {
+                        tracing::warn!(
+                            "IPC: live session reached the [dmenu] max_items limit of {}",
+                            max_items
+                        );
                     }
-                    Ok(None) | Err(_) => {
-                        // Cancelled or channel dropped — just close
-                        let mut writer = stream;
-                        let _ = writer.shutdown().await;
+                    dropped += lines.len() - room;
+                }
+                let lines: Vec<String> = lines.into_iter().take(room).collect();
+                if lines.is_empty() {
+                    continue;
+                }
+                let items = to_source_items(&lines, raw_lines.len(), is_jsonl);
+                raw_lines.extend(lines);
+                let _ = sender.send(Message::DmenuAppend(items)).await;
+            }
+        }
+    };
+    let stream = reader.into_inner();
+    match response {
+        Ok(Some(selection)) if !selection.ids.is_empty() => {
+            let mut payload = String::new();
+            if context.report_action {
+                payload.push_str(selection.action);
+                payload.push('\n');
+            }
+            // One line per selected item (several only in `multi` sessions)
+            for item_id in selection.ids {
+                let Some(line) = raw_lines.get(item_id) else {
+                    tracing::warn!(
+                        "IPC: item id {} out of range (raw_lines len={})",
+                        item_id,
+                        raw_lines.len()
+                    );
+                    continue;
+                };
+                let response = match context.response {
+                    _ if !context.fields.is_empty() => {
+                        selected_fields(line, is_jsonl, &context.fields)
+                    }
+                    ResponseFormat::Line => line.clone(),
+                    ResponseFormat::Indexed => format!("{item_id}\t{line}"),
+                    ResponseFormat::Index => item_id.to_string(),
+                    ResponseFormat::Json => {
+                        let response = IpcResponse {
+                            index: item_id,
+                            value: line.clone(),
+                            action: selection.action.to_string(),
+                        };
+                        serde_json::to_string(&response).unwrap_or_default()
                     }
+                };
+                payload.push_str(&response);
+                payload.push('\n');
+            }
+
+            let mut writer = stream;
+            if let Err(e) = writer.write_all(payload.as_bytes()).await {
+                tracing::error!("IPC write error: {}", e);
+            }
+            let _ = writer.shutdown().await;
+        }
+        _ => {
+            // Cancelled or channel dropped — just close
+            let mut writer = stream;
+            let _ = writer.shutdown().await;
+        }
+    }
+}
+
+/// Answer a control request. `ping` never touches the app state, so it works during
+/// a session; `show`/`hide` are forwarded to the app.
+async fn handle_command(
+    command: IpcCommand,
+    mut stream: UnixStream,
+    sender: &mut iced::futures::channel::mpsc::Sender<Message>,
+) {
+    tracing::debug!("IPC command: {:?}", command);
+    let message = match command {
+        IpcCommand::Ping => None,
+        IpcCommand::Show { mode } => Some(Message::ShowMode(mode)),
+        IpcCommand::Hide => Some(Message::Hide),
+    };
+    let ok = match message {
+        Some(message) => sender.send(message).await.is_ok(),
+        None => true,
+    };
+    let reply = IpcReply { ok };
+    let reply = serde_json::to_string(&reply).unwrap_or_default();
+    if let Err(e) = stream.write_all(format!("{reply}\n").as_bytes()).await {
+        tracing::error!("IPC write error: {}", e);
+    }
+    let _ = stream.shutdown().await;
+}
+
+/// Read the next line plus any further complete lines already buffered, so a burst of
+/// output becomes a single append. Returns no lines at EOF.
+async fn read_available_lines(reader: &mut BufReader<UnixStream>) -> Vec<String> {
+    let mut lines = Vec::new();
+    loop {
+        let mut line = String::new();
+        match reader.read_line(&mut line).await {
+            Ok(0) => break,
+            Ok(_) => {
+                let trimmed = line.trim_end_matches('\n').trim_end_matches('\r');
+                if !trimmed.is_empty() {
+                    lines.push(trimmed.to_string());
                 }
             }
-        },
-    )
+            Err(e) => {
+                tracing::error!("IPC read error: {}", e);
+                break;
+            }
+        }
+        if !lines.is_empty() && !reader.buffer().contains(&b'\n') {
+            break;
+        }
+    }
+    lines
+}
+
+/// The `fields` response for a selected raw line: a JSON object mapping each field to
+/// its value (`DmenuItem::get_field`). A text line is an item with only a title.
+fn selected_fields(line: &str, is_jsonl: bool, fields: &[String]) -> String {
+    let parsed = is_jsonl
+        .then(|| serde_json::from_str::<DmenuItem>(line).ok())
+        .flatten();
+    let item = parsed.unwrap_or_else(|| DmenuItem {
+        title: line.to_string(),
+        subtitle: None,
+        icon_path: None,
+        data: None,
+    });
+    let object: serde_json::Map<String, serde_json::Value> = fields
+        .iter()
+        .map(|field| (field.clone(), item.get_field(field).into()))
+        .collect();
+    serde_json::to_string(&object).unwrap_or_default()
+}
+
+/// Convert raw lines to SourceItems based on format.
+/// Each item's `id` field stores its raw_lines index (`first_id` + position).
+fn to_source_items(raw_lines: &[String], first_id: usize, is_jsonl: bool) -> Vec<SourceItem> {
+    if is_jsonl {
+        raw_lines
+            .iter()
+            .enumerate()
+            .filter_map(
+                |(idx, line)| match serde_json::from_str::<DmenuItem>(line) {
+                    Ok(di) => Some(SourceItem {
+                        id: Some(first_id + idx),
+                        title: di.title.clone(),
+                        subtitle: di.subtitle.clone(),
+                        exec_path: di.get_field("data"),
+                        source_name: "dmenu".to_string(),
+                        icon: None,
+                    }),
+                    Err(e) => {
+                        tracing::debug!("Failed to parse JSONL line: {}", e);
+                        None
+                    }
+                },
+            )
+            .collect()
+    } else {
+        raw_lines
+            .iter()
+            .enumerate()
+            .map(|(idx, title)| SourceItem {
+                id: Some(first_id + idx),
+                title: title.clone(),
+                subtitle: None,
+                exec_path: String::new(),
+                source_name: "dmenu".to_string(),
+                icon: None,
+            })
+            .collect()
+    }
 }
@@ -5,6 +5,7 @@ mod hotkey;
 mod icon;
 mod ipc_server;
 mod matcher;
+mod signal;
 mod ui;
 
 use std::sync::Mutex;
@@ -13,10 +14,15 @@ use std::{process, thread, time::Duration};
 use global_hotkey::GlobalHotKeyManager;
 
 use crate::app::State;
-use heats_core::config::Config;
+use heats_core::config::{Config, LogConfig};
 use heats_core::ipc;
 
-type BootParams = (Config, GlobalHotKeyManager, Vec<(u32, String)>);
+type BootParams = (
+    Config,
+    Option<String>,
+    GlobalHotKeyManager,
+    Vec<(u32, String)>,
+);
 
 static BOOT_PARAMS: Mutex<Option<BootParams>> = Mutex::new(None);
 
@@ -26,17 +32,23 @@ fn boot() -> (State, iced::Task<app::Message>) {
         .unwrap()
         .take()
         .expect("boot() called more than once");
-    State::new(params.0, params.1, params.2)
+    State::new(params.0, params.1, params.2, params.3)
 }
 
 fn main() {
     let args: Vec<String> = std::env::args().collect();
 
-    // Parse --config <path> option (can appear anywhere before subcommand)
+    // Parse --config <path> and --foreground options (can appear anywhere before subcommand)
     let mut config_path: Option<std::path::PathBuf> = None;
+    let mut foreground = false;
     let mut rest_args: Vec<&str> = Vec::new();
     let mut i = 1;
     while i < args.len() {
+        if args[i] == "--foreground" || args[i] == "-f" {
+            foreground = true;
+            i += 1;
+            continue;
+        }
         if args[i] == "--config" {
             if let Some(path) = args.get(i + 1) {
                 config_path = Some(std::path::PathBuf::from(path));
@@ -56,6 +68,15 @@ fn main() {
     match subcmd {
         Some("stop") => cmd_stop(),
         Some("restart") => cmd_restart(),
+        Some("doctor") => cmd_doctor(config_path),
+        Some("version" | "--version") => cmd_version(),
+        Some("config") => match rest_args.get(1).copied() {
+            Some("check") => cmd_config_check(config_path),
+            _ => {
+                eprintln!("Usage: heatsd config check");
+                process::exit(2);
+            }
+        },
         Some("service") => {
             let action = rest_args.get(1).copied();
             match action {
@@ -69,27 +90,34 @@ fn main() {
         }
         Some(other) => {
             eprintln!("Unknown command: {other}");
-            eprintln!("Usage: heatsd [--config <path>] [stop|restart|service <install|uninstall>]");
+            eprintln!(
+                "Usage: heatsd [--config <path>] [-f|--foreground] \
+                 [stop|restart|doctor|version|config check|service <install|uninstall>]"
+            );
             process::exit(2);
         }
-        None => cmd_run(config_path),
+        None => cmd_run(config_path, foreground),
     }
 }
 
 // ---- Run (default) ----
 
-fn cmd_run(config_path: Option<std::path::PathBuf>) {
-    tracing_subscriber::fmt()
-        .with_env_filter(
-            tracing_subscriber::EnvFilter::try_from_default_env()
-                .unwrap_or_else(|_| tracing_subscriber::EnvFilter::new("heats=info")),
-        )
-        .init();
-
-    let config = match &config_path {
-        Some(path) => heats_core::config::load_from(path),
-        None => heats_core::config::load(),
-    };
+/// Run the launcher. With `foreground` (for debugging from a terminal), logs go to
+/// stderr at debug level unless RUST_LOG says otherwise.
+fn cmd_run(config_path: Option<std::path::PathBuf>, foreground: bool) {
+    let config_path = config_path.unwrap_or_else(heats_core::config::config_path);
+
+    // [log] is needed before the subscriber exists; the full load below reports errors
+    let log_config = heats_core::config::try_load_from(&config_path)
+        .map(|config| config.log)
+        .unwrap_or_default();
+    init_logging(&log_config, foreground);
+
+    let (config, config_error) = heats_core::config::load_reporting(&config_path);
+    let config_error = config_error.map(|e| format!("{}: {e}", config_path.display()));
+
+    // Before any threads are spawned: providers need the user's PATH, not launchd's
+    command::init_path(&config.env);
 
     // Clean up stale socket from previous run
     let sock = ipc::socket_path();
@@ -98,23 +126,25 @@ fn cmd_run(config_path: Option<std::path::PathBuf>) {
         tracing::info!("Removed stale socket: {}", sock.display());
     }
 
+    // Drop cached items of providers removed from the config. Not while the defaults
+    // stand in for a broken config.toml: that would wipe every user provider's cache.
+    if config_error.is_none() {
+        heats_core::cache::prune_orphans(&config);
+    }
+
     // Write PID file
     ipc::write_pid();
 
-    // Set up signal handler for graceful shutdown
-    let _ = ctrlc::set_handler(move || {
-        ipc::remove_pid();
-        let sock = ipc::socket_path();
-        let _ = std::fs::remove_file(&sock);
-        process::exit(0);
-    });
+    // SIGINT/SIGTERM are routed into the app, which exits the iced daemon so the
+    // cleanup below still runs
+    signal::install_handler();
 
     // Initialize global hotkey manager on the main thread (macOS requirement)
     let (manager, hotkey_modes) = hotkey::init_manager(&config.mode);
 
     tracing::info!("Starting Heats launcher");
 
-    *BOOT_PARAMS.lock().unwrap() = Some((config, manager, hotkey_modes));
+    *BOOT_PARAMS.lock().unwrap() = Some((config, config_error, manager, hotkey_modes));
 
     let result = iced::daemon(boot, State::update, State::view)
         .title(State::title)
@@ -123,7 +153,7 @@ fn cmd_run(config_path: Option<std::path::PathBuf>) {
         .style(State::style)
         .run();
 
-    // Cleanup on normal exit
+    // Cleanup on exit (including signal-triggered shutdown)
     ipc::remove_pid();
     let _ = std::fs::remove_file(ipc::socket_path());
 
@@ -133,6 +163,49 @@ fn cmd_run(config_path: Option<std::path::PathBuf>) {
     }
 }
 
+/// Set up tracing from `[log]`: level (unless RUST_LOG is set) and an optional log file.
+/// `--foreground` overrides both with debug output on stderr.
+fn init_logging(log_config: &LogConfig, foreground: bool) {
+    let default_filter = if foreground {
+        "heats=debug".to_string()
+    } else {
+        log_config.filter()
+    };
+    let subscriber = tracing_subscriber::fmt().with_env_filter(
+        tracing_subscriber::EnvFilter::try_from_default_env()
+            .unwrap_or_else(|_| tracing_subscriber::EnvFilter::new(default_filter)),
+    );
+    if foreground {
+        subscriber.with_writer(std::io::stderr).init();
+        return;
+    }
+
+    let Some(path) = log_config.file_path() else {
+        subscriber.init();
+        return;
+    };
+    match open_log_file(&path) {
+        Ok(file) => subscriber
+            .with_ansi(false)
+            .with_writer(Mutex::new(file))
+            .init(),
+        Err(e) => {
+            subscriber.init();
+            tracing::warn!("Failed to open log file {}: {e}", path.display());
+        }
+    }
+}
+
+fn open_log_file(path: &std::path::Path) -> std::io::Result<std::fs::File> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+}
+
 // ---- Stop ----
 
 fn cmd_stop() {
@@ -192,6 +265,175 @@ fn cmd_restart() {
     }
 }
 
+// ---- Version ----
+
+fn cmd_version() {
+    let version = env!("CARGO_PKG_VERSION");
+    match env!("HEATS_GIT_HASH") {
+        "" => println!("heatsd {version}"),
+        hash => println!("heatsd {version} ({hash})"),
+    }
+}
+
+// ---- Config check ----
+
+/// Parse the config file and print the exact error (with line/column), plus references
+/// to undefined providers/evaluators. Exits 1 on any problem.
+fn cmd_config_check(config_path: Option<std::path::PathBuf>) {
+    let path = config_path.unwrap_or_else(heats_core::config::config_path);
+    if !path.exists() {
+        println!(
+            "{} not found, heatsd uses the default config",
+            path.display()
+        );
+        return;
+    }
+    match heats_core::config::try_load_from(&path) {
+        Ok(config) => {
+            // Not a problem: heatsd prunes these on the next start
+            for orphan in heats_core::cache::orphaned_entries(&config) {
+                println!(
+                    "{}: orphaned cache (provider not in config), pruned on the next start",
+                    orphan.display()
+                );
+            }
+            let problems = config.validate();
+            if problems.is_empty() {
+                println!("{}: OK", path.display());
+                return;
+            }
+            for problem in &problems {
+                eprintln!("{}: {problem}", path.display());
+            }
+            process::exit(1);
+        }
+        Err(e) => {
+            eprintln!("{}: {e}", path.display());
+            process::exit(1);
+        }
+    }
+}
+
+// ---- Doctor ----
+
+fn cmd_doctor(config_path: Option<std::path::PathBuf>) {
+    let mut failed = false;
+    // Whether `config` is the user's own (not the defaults standing in for a broken file)
+    let mut config_parsed = true;
+
+    println!("Config");
+    let path = config_path.unwrap_or_else(heats_core::config::config_path);
+    let config = if !path.exists() {
+        report(
+            true,
+            &format!("{} not found, using defaults", path.display()),
+            "",
+        );
+        Config::default()
+    } else {
+        match heats_core::config::try_load_from(&path) {
+            Ok(config) => {
+                report(true, &format!("{} parsed", path.display()), "");
+                config
+            }
+            Err(e) => {
+                report(
+                    false,
+                    &format!("{}: {e}", path.display()),
+                    "Fix the error above; heatsd falls back to the default config until then",
+                );
+                failed = true;
+                config_parsed = false;
+                Config::default()
+            }
+        }
+    };
+
+    for problem in config.validate() {
+        report(false, &problem, "Define it or remove it from the mode");
+        failed = true;
+    }
+
+    println!("Permissions");
+    let screen_capture = heats_core::platform::macos::screen_capture_access_granted();
+    report(
+        screen_capture,
+        "Screen Recording",
+        "Grant access in System Settings → Privacy & Security → Screen Recording \
+         (window titles are hidden without it)",
+    );
+    failed |= !screen_capture;
+
+    println!("Commands");
+    command::init_path(&config.env);
+    let mut commands = std::collections::BTreeSet::new();
+    // Shell sources are command lines, not program names, so they can't be checked here
+    for provider in config.provider.values() {
+        if !provider.shell {
+            for source in provider.source_commands() {
+                commands.extend(source.first().cloned());
+            }
+        }
+        commands.extend(provider.action.first().cloned());
+    }
+    for evaluator in config.evaluator.values() {
+        if !evaluator.shell {
+            commands.extend(evaluator.source.first().cloned());
+        }
+        commands.extend(evaluator.action.first().cloned());
+    }
+    for name in &commands {
+        let resolved = command::resolve_command(name);
+        let found = command_exists(&resolved);
+        report(
+            found,
+            &format!("{name} → {resolved}"),
+            "Install the command next to heatsd or make sure it is on PATH",
+        );
+        failed |= !found;
+    }
+
+    println!("Cache");
+    let orphans = if config_parsed {
+        heats_core::cache::orphaned_entries(&config)
+    } else {
+        // Against the defaults every user provider would look orphaned
+        println!("  [skip] Orphaned cache entries (config.toml doesn't parse)");
+        Vec::new()
+    };
+    if orphans.is_empty() && config_parsed {
+        report(true, "No orphaned cache entries", "");
+    }
+    for path in &orphans {
+        // Not a failure: heatsd prunes these on the next start
+        println!("  [warn] {} (provider not in config)", path.display());
+    }
+
+    if failed {
+        process::exit(1);
+    }
+}
+
+/// Print a single checklist line, with a remediation hint on failure.
+fn report(ok: bool, label: &str, hint: &str) {
+    if ok {
+        println!("  [ok]   {label}");
+    } else {
+        println!("  [fail] {label}");
+        println!("         {hint}");
+    }
+}
+
+/// Check whether a command resolved by `resolve_command` can actually be executed.
+fn command_exists(resolved: &str) -> bool {
+    if resolved.contains('/') {
+        return std::path::Path::new(resolved).is_file();
+    }
+    std::env::var_os("PATH")
+        .map(|paths| std::env::split_paths(&paths).any(|dir| dir.join(resolved).is_file()))
+        .unwrap_or(false)
+}
+
 // ---- Service (launchd) ----
 
 const PLIST_LABEL: &str = "com.heats.daemon";
@@ -228,11 +470,6 @@ fn cmd_service_install() {
     <string>/tmp/heatsd.out.log</string>
     <key>StandardErrorPath</key>
     <string>/tmp/heatsd.err.log</string>
-    <key>EnvironmentVariables</key>
-    <dict>
-        <key>RUST_LOG</key>
-        <string>heats=info</string>
-    </dict>
 </dict>
 </plist>"#
     );
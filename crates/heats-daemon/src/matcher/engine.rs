@@ -1,72 +1,147 @@
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 
 use nucleo::pattern::{CaseMatching, Normalization};
 use nucleo::{Config, Nucleo};
 
+use heats_core::config::{CaseMode, MatcherConfig};
 use heats_core::source::SourceItem;
 
-/// Wrapper around nucleo for fuzzy matching
+/// Wrapper around nucleo for fuzzy matching.
+/// Items are shared with `State` through `Arc`, so handing them over and reading
+/// matches back only bumps reference counts.
 pub struct Matcher {
-    nucleo: Nucleo<SourceItem>,
+    nucleo: Nucleo<Arc<SourceItem>>,
     last_query: String,
+    /// Whether the last tick left matching unfinished
+    running: bool,
+    /// Whether there is work for `tick` to pick up: set when items or the query change
+    /// (and by nucleo's notify callback), cleared once a tick finds matching settled
+    pending: Arc<AtomicBool>,
+    case_matching: CaseMatching,
+    normalization: Normalization,
+    /// Scratch matcher for re-scoring snapshot items (the snapshot does not expose scores)
+    scorer: nucleo::Matcher,
 }
 
 impl Default for Matcher {
     fn default() -> Self {
-        Self::new()
+        Self::new(&MatcherConfig::default())
     }
 }
 
 impl Matcher {
-    pub fn new() -> Self {
+    pub fn new(config: &MatcherConfig) -> Self {
+        let mut nucleo_config = Config::DEFAULT;
+        if config.match_paths {
+            nucleo_config.set_match_paths();
+        }
+        nucleo_config.prefer_prefix = config.prefer_prefix;
+
+        let pending = Arc::new(AtomicBool::new(false));
+        let notify = {
+            let pending = pending.clone();
+            Arc::new(move || pending.store(true, Ordering::Release))
+        };
         let nucleo = Nucleo::new(
-            Config::DEFAULT,
-            Arc::new(|| {}), // notify callback (unused; we tick on a timer)
-            None,            // auto thread count
-            1,               // single column
+            nucleo_config.clone(),
+            notify, // workers have new results for the next tick
+            None,   // auto thread count
+            1,      // single column
         );
         Self {
             nucleo,
             last_query: String::new(),
+            running: false,
+            pending,
+            case_matching: match config.case {
+                CaseMode::Smart => CaseMatching::Smart,
+                CaseMode::Ignore => CaseMatching::Ignore,
+                CaseMode::Respect => CaseMatching::Respect,
+            },
+            normalization: if config.normalize {
+                Normalization::Smart
+            } else {
+                Normalization::Never
+            },
+            scorer: nucleo::Matcher::new(nucleo_config),
         }
     }
 
     /// Inject items into the matcher. Clears existing items first.
-    pub fn set_items(&mut self, items: Vec<SourceItem>) {
+    pub fn set_items(&mut self, items: Vec<Arc<SourceItem>>) {
+        self.inject(items, false);
+    }
+
+    /// Like `set_items`, but matches against "title subtitle" so either can be searched.
+    pub fn set_items_with_subtitle(&mut self, items: Vec<Arc<SourceItem>>) {
+        self.inject(items, true);
+    }
+
+    /// Add items matched like `set_items_with_subtitle`, keeping the existing ones.
+    pub fn append_items_with_subtitle(&mut self, items: Vec<Arc<SourceItem>>) {
+        self.push(items, true);
+    }
+
+    fn inject(&mut self, items: Vec<Arc<SourceItem>>, include_subtitle: bool) {
         self.nucleo.restart(true);
+        self.push(items, include_subtitle);
+    }
+
+    fn push(&mut self, items: Vec<Arc<SourceItem>>, include_subtitle: bool) {
+        self.pending.store(true, Ordering::Release);
         let injector = self.nucleo.injector();
         for item in items {
-            let title = item.title.clone();
+            let haystack = match &item.subtitle {
+                Some(subtitle) if include_subtitle => format!("{} {}", item.title, subtitle),
+                _ => item.title.clone(),
+            };
             injector.push(item, |_item, cols| {
-                cols[0] = title.as_str().into();
+                cols[0] = haystack.as_str().into();
             });
         }
     }
 
-    /// Update the search query
+    /// Update the search query.
+    /// nucleo's pattern parser splits the query on whitespace into separate atoms that
+    /// must all match (in any order), so "chrome git" finds "GitHub - Google Chrome".
+    /// Appending to the query (including starting a new word) only narrows the matches,
+    /// which lets nucleo rescore incrementally.
     pub fn update_query(&mut self, query: &str) {
         if query == self.last_query {
             return;
         }
         let is_append = query.starts_with(&self.last_query) && !self.last_query.is_empty();
-        self.nucleo.pattern.reparse(
-            0,
-            query,
-            CaseMatching::Smart,
-            Normalization::Smart,
-            is_append,
-        );
+        self.nucleo
+            .pattern
+            .reparse(0, query, self.case_matching, self.normalization, is_append);
         self.last_query = query.to_string();
+        self.pending.store(true, Ordering::Release);
     }
 
     /// Tick the matcher, returning whether results changed
     pub fn tick(&mut self) -> bool {
+        self.pending.store(false, Ordering::Release);
         let status = self.nucleo.tick(10);
+        self.running = status.running;
+        if status.running {
+            self.pending.store(true, Ordering::Release);
+        }
         status.changed
     }
 
+    /// Whether `tick` needs to be called (items or query changed since matching settled)
+    pub fn needs_tick(&self) -> bool {
+        self.pending.load(Ordering::Acquire)
+    }
+
+    /// Whether matching for the current query is still in progress
+    pub fn is_running(&self) -> bool {
+        self.running
+    }
+
     /// Get the current matched results (sorted by score, best first)
-    pub fn results(&self, max: usize) -> Vec<SourceItem> {
+    pub fn results(&self, max: usize) -> Vec<Arc<SourceItem>> {
         let snapshot = self.nucleo.snapshot();
         let count = (snapshot.matched_item_count() as usize).min(max);
         snapshot
@@ -75,6 +150,24 @@ impl Matcher {
             .collect()
     }
 
+    /// Like `results`, but drops items scoring below `min_score`.
+    /// Matches are sorted by score, so this stops at the first weak one.
+    pub fn results_with_threshold(&mut self, max: usize, min_score: u32) -> Vec<Arc<SourceItem>> {
+        let snapshot = self.nucleo.snapshot();
+        let pattern = snapshot.pattern();
+        let scorer = &mut self.scorer;
+        snapshot
+            .matched_items(..)
+            .take_while(|item| {
+                pattern
+                    .score(item.matcher_columns, scorer)
+                    .is_some_and(|score| score >= min_score)
+            })
+            .take(max)
+            .map(|item| item.data.clone())
+            .collect()
+    }
+
     /// Check if the query is empty (meaning all items should be shown)
     pub fn query_is_empty(&self) -> bool {
         self.last_query.is_empty()
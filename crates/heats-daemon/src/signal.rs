@@ -0,0 +1,46 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+use iced::futures::SinkExt;
+use iced::stream::channel;
+use iced::Subscription;
+
+use heats_core::ipc;
+
+/// Set by the signal handler, picked up by the subscription on the iced side.
+static SHUTDOWN_REQUESTED: AtomicBool = AtomicBool::new(false);
+
+/// Install the SIGINT/SIGTERM handler. The first signal asks the app to shut down
+/// through the normal iced exit path; a second one exits immediately in case the
+/// app is stuck.
+pub fn install_handler() {
+    let result = ctrlc::set_handler(|| {
+        if SHUTDOWN_REQUESTED.swap(true, Ordering::SeqCst) {
+            ipc::remove_pid();
+            let _ = std::fs::remove_file(ipc::socket_path());
+            std::process::exit(0);
+        }
+    });
+    if let Err(e) = result {
+        tracing::warn!("Failed to install signal handler: {e}");
+    }
+}
+
+/// Emits once when a shutdown signal has been received.
+pub fn subscription() -> Subscription<()> {
+    Subscription::run(shutdown_stream)
+}
+
+fn shutdown_stream() -> impl iced::futures::Stream<Item = ()> {
+    channel(
+        1,
+        |mut sender: iced::futures::channel::mpsc::Sender<()>| async move {
+            while !SHUTDOWN_REQUESTED.load(Ordering::SeqCst) {
+                tokio::time::sleep(Duration::from_millis(100)).await;
+            }
+            let _ = sender.send(()).await;
+            // Keep the stream alive so the subscription is not restarted
+            std::future::pending::<()>().await;
+        },
+    )
+}
@@ -0,0 +1,21 @@
+use iced::widget::{row, text};
+use iced::Element;
+
+use crate::app::Message;
+use crate::ui::theme;
+
+/// Build the footer listing available keys as "key label" pairs.
+pub fn view<'a>(hints: &[(&'a str, &'a str)]) -> Element<'a, Message> {
+    let mut hint_row = row![].spacing(12);
+
+    for &(key, label) in hints {
+        let hint = row![
+            text(key).size(11).color(theme::TEXT_PRIMARY),
+            text(label).size(11).color(theme::TEXT_SECONDARY),
+        ]
+        .spacing(4);
+        hint_row = hint_row.push(hint);
+    }
+
+    hint_row.into()
+}
@@ -1,3 +1,5 @@
+pub mod hint_bar;
+pub mod preview;
 pub mod result_list;
 pub mod search_input;
 pub mod tab_bar;
@@ -0,0 +1,20 @@
+use iced::widget::{container, scrollable, text};
+use iced::{Element, Fill, Font, Padding};
+
+use crate::app::Message;
+use crate::ui::theme;
+
+/// Build the preview pane: `content` in a monospace font, scrollable when it overflows.
+pub fn view<'a>(content: String) -> Element<'a, Message> {
+    let body = text(content)
+        .size(12)
+        .font(Font::MONOSPACE)
+        .color(theme::TEXT_PRIMARY);
+
+    container(scrollable(body).width(Fill).height(Fill))
+        .padding(Padding::new(8.0))
+        .width(Fill)
+        .height(Fill)
+        .style(theme::preview_panel)
+        .into()
+}
@@ -1,58 +1,197 @@
-use iced::widget::{column, container, image, mouse_area, row, text, Column};
+use std::collections::{HashMap, HashSet};
+
+use iced::widget::{column, container, image, mouse_area, row, rule, space, text, Column};
 use iced::{Element, Fill, Padding};
 
 use crate::app::Message;
 use crate::ui::theme;
 use heats_core::source::{IconData, SourceItem};
 
-/// Estimated row height in pixels (padding + title + subtitle + spacing)
-const ROW_HEIGHT_ESTIMATE: f32 = 54.0;
+/// Font size of a row's title
+const TITLE_SIZE: f32 = 16.0;
+/// Font size of a row's subtitle
+const SUBTITLE_SIZE: f32 = 12.0;
+/// Gap between title and subtitle, and between rows
+const ROW_SPACING: f32 = 2.0;
+/// Line height relative to the font size (iced's default `LineHeight`)
+const LINE_HEIGHT: f32 = 1.3;
 /// Fixed overhead: outer padding (12*2) + search input (~44) + spacing (8)
 const LAYOUT_OVERHEAD: f32 = 76.0;
 /// Extra overhead when the tab bar is shown (~26px text + spacing)
 const TAB_BAR_OVERHEAD: f32 = 30.0;
+/// Extra overhead when the key hint footer is shown (~16px text + spacing)
+const HINT_BAR_OVERHEAD: f32 = 24.0;
+
+/// Estimated height of a group header row (`group_by_source`)
+const HEADER_HEIGHT_ESTIMATE: f32 = 22.0;
+
+/// Layout and decoration options for the result list
+pub struct ListOptions<'a> {
+    /// Window height (the maximum height with `auto_height`)
+    pub window_height: f32,
+    pub has_tabs: bool,
+    /// Whether the key hint footer (`show_hints`) is shown
+    pub has_hints: bool,
+    /// Number of evaluator rows at the top; a separator follows them
+    pub eval_count: usize,
+    /// Source name → display label, for source tags and group headers
+    pub labels: &'a HashMap<String, String>,
+    /// Show each row's source as a dim right-aligned tag
+    pub show_source_tag: bool,
+    /// Insert a header row above each run of items from the same source
+    pub group_by_source: bool,
+    /// Item IDs marked in a multi-select dmenu session (check mark)
+    pub marked: &'a [usize],
+    /// Item IDs pinned by the current mode (star)
+    pub pinned: &'a HashSet<usize>,
+    /// Space above and below each row's content (`window.row_padding`)
+    pub row_padding: f32,
+    /// Index of the row under the mouse cursor (lighter highlight than the selection)
+    pub hovered: Option<usize>,
+}
+
+/// Height of a result row with a subtitle, including the gap to the next row.
+/// Shared by the visible-count math and the row layout so they never drift apart.
+fn row_height(options: &ListOptions) -> f32 {
+    options.row_padding * 2.0
+        + TITLE_SIZE * LINE_HEIGHT
+        + ROW_SPACING
+        + SUBTITLE_SIZE * LINE_HEIGHT
+        + ROW_SPACING
+}
+
+/// Height taken by everything but the result rows.
+fn overhead(options: &ListOptions) -> f32 {
+    let mut overhead = LAYOUT_OVERHEAD;
+    if options.has_tabs {
+        overhead += TAB_BAR_OVERHEAD;
+    }
+    if options.has_hints {
+        overhead += HINT_BAR_OVERHEAD;
+    }
+    overhead
+}
 
 /// Calculate how many items fit in the available window height.
-fn visible_count(window_height: f32, has_tabs: bool) -> usize {
-    let overhead = LAYOUT_OVERHEAD + if has_tabs { TAB_BAR_OVERHEAD } else { 0.0 };
-    let available = (window_height - overhead).max(0.0);
-    let count = (available / ROW_HEIGHT_ESTIMATE) as usize;
+fn visible_count(window_height: f32, options: &ListOptions) -> usize {
+    let available = (window_height - overhead(options)).max(0.0);
+    let count = (available / row_height(options)) as usize;
     count.max(1)
 }
 
+/// Number of group header rows: one per run of consecutive items from the same source.
+fn header_count(results: &[&SourceItem], options: &ListOptions) -> usize {
+    if !options.group_by_source {
+        return 0;
+    }
+    let headers = results
+        .chunk_by(|a, b| a.source_name == b.source_name)
+        .count();
+    headers.min(visible_count(options.window_height, options))
+}
+
+/// How many item rows fit once group headers have taken their space.
+fn visible_rows(results: &[&SourceItem], options: &ListOptions) -> usize {
+    let headers = header_count(results, options) as f32 * HEADER_HEIGHT_ESTIMATE;
+    visible_count(options.window_height - headers, options)
+}
+
+/// Window height that fits the rows (and group headers): at most
+/// `options.window_height`, and at least the search box alone when there are no rows.
+pub fn fit_height(results: &[&SourceItem], options: &ListOptions) -> f32 {
+    let rows = results.len().min(visible_rows(results, options));
+    let headers = header_count(results, options).min(rows);
+    let height = overhead(options)
+        + rows as f32 * row_height(options)
+        + headers as f32 * HEADER_HEIGHT_ESTIMATE;
+    height.min(options.window_height)
+}
+
+/// Compute the visible window (`start..end`) that keeps the selected item in view.
+pub fn visible_range(
+    selected_index: usize,
+    results: &[&SourceItem],
+    options: &ListOptions,
+) -> std::ops::Range<usize> {
+    let max_visible = visible_rows(results, options);
+    let start = (selected_index + 1).saturating_sub(max_visible);
+    let end = (start + max_visible).min(results.len());
+    start..end
+}
+
+/// Resolve the tag label for a source: exact mapping, then "eval:*", then the raw name.
+fn source_label(source_name: &str, labels: &HashMap<String, String>) -> String {
+    labels
+        .get(source_name)
+        .or_else(|| {
+            source_name
+                .starts_with("eval:")
+                .then(|| labels.get("eval:*"))
+                .flatten()
+        })
+        .cloned()
+        .unwrap_or_else(|| source_name.to_string())
+}
+
 /// Build the result list widget.
 /// Shows a window of items around the selected index, sized to fit the window.
+/// Group headers and the evaluator separator are display-only: indices always
+/// refer to `results`.
 pub fn view<'a>(
     results: &[&'a SourceItem],
     selected_index: usize,
-    window_height: f32,
-    has_tabs: bool,
+    options: &ListOptions,
 ) -> Element<'a, Message> {
     if results.is_empty() {
         return column![].into();
     }
 
-    let max_visible = visible_count(window_height, has_tabs);
-
     // Calculate visible window: keep selected item in view
-    let start = (selected_index + 1).saturating_sub(max_visible);
-    let end = (start + max_visible).min(results.len());
+    let range = visible_range(selected_index, results, options);
+    let (start, end) = (range.start, range.end);
 
-    let mut rows = Column::new().spacing(2);
+    let mut rows = Column::new().spacing(ROW_SPACING);
     for (i, item) in results.iter().enumerate().take(end).skip(start) {
-        let is_selected = i == selected_index;
-        let style = if is_selected {
+        // Separate evaluator results from provider results (only when both are visible)
+        if i > start && i == options.eval_count {
+            rows = rows.push(rule::horizontal(1).style(theme::separator));
+        }
+
+        let starts_group = i == start || results[i - 1].source_name != item.source_name;
+        if options.group_by_source && starts_group {
+            let header = text(source_label(&item.source_name, options.labels))
+                .size(11)
+                .color(theme::TEXT_TAG);
+            rows = rows.push(container(header).padding(Padding::from([4, 12])));
+        }
+
+        let style = if i == selected_index {
             theme::result_row_selected as fn(&iced::Theme) -> container::Style
+        } else if options.hovered == Some(i) {
+            theme::result_row_hovered
         } else {
             theme::result_row
         };
 
-        let name = text(&item.title).size(16).color(theme::TEXT_PRIMARY);
+        let indicator = if item.id.is_some_and(|id| options.marked.contains(&id)) {
+            Some("✓")
+        } else if item.id.is_some_and(|id| options.pinned.contains(&id)) {
+            Some("★")
+        } else {
+            None
+        };
+        let name = match indicator {
+            Some(indicator) => text(format!("{indicator} {}", item.title)),
+            None => text(&item.title),
+        }
+        .size(TITLE_SIZE)
+        .color(theme::TEXT_PRIMARY);
 
         let text_column: Element<'a, Message> = if let Some(subtitle) = &item.subtitle {
-            column![name, text(subtitle).size(12).color(theme::TEXT_SECONDARY)]
-                .spacing(2)
-                .into()
+            let subtitle = text(subtitle)
+                .size(SUBTITLE_SIZE)
+                .color(theme::TEXT_SECONDARY);
+            column![name, subtitle].spacing(ROW_SPACING).into()
         } else {
             name.into()
         };
@@ -78,12 +217,27 @@ pub fn view<'a>(
             None => text_column,
         };
 
+        let row_content = if options.show_source_tag {
+            let tag = text(source_label(&item.source_name, options.labels))
+                .size(11)
+                .color(theme::TEXT_TAG);
+            row![row_content, space::horizontal(), tag]
+                .spacing(8)
+                .align_y(iced::Alignment::Center)
+                .into()
+        } else {
+            row_content
+        };
+
         let row = container(row_content)
-            .padding(Padding::from([6, 12]))
+            .padding(Padding::from([options.row_padding, 12.0]))
             .width(Fill)
             .style(style);
 
-        let clickable = mouse_area(row).on_press(Message::SelectAndExecute(i));
+        let clickable = mouse_area(row)
+            .on_press(Message::SelectAndExecute(i))
+            .on_enter(Message::Hover(i))
+            .on_exit(Message::Unhover(i));
 
         rows = rows.push(clickable);
     }
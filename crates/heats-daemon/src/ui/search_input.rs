@@ -1,5 +1,5 @@
-use iced::widget::text_input;
-use iced::{Element, Fill};
+use iced::widget::{container, mouse_area, stack, text, text_input};
+use iced::{mouse, Element, Fill, Font, Padding};
 
 use crate::app::Message;
 use crate::ui::theme;
@@ -7,15 +7,49 @@ use crate::ui::theme;
 /// The search input ID for focus management
 pub const SEARCH_INPUT_ID: &str = "heats-search-input";
 
-/// Build the search input widget
-pub fn view(query: &str) -> Element<'_, Message> {
-    text_input("Type to search...", query)
+/// Placeholder shown when no prompt is given
+pub const DEFAULT_PLACEHOLDER: &str = "Type to search...";
+
+/// Build the search input widget. `icon` is drawn at the left edge; `secure` masks
+/// the typed text. A clickable "×" at the right edge clears a non-empty query.
+pub fn view<'a>(
+    query: &'a str,
+    placeholder: &'a str,
+    icon: Option<char>,
+    secure: bool,
+) -> Element<'a, Message> {
+    let mut input = text_input(placeholder, query);
+    if let Some(code_point) = icon {
+        input = input.icon(text_input::Icon {
+            font: Font::DEFAULT,
+            code_point,
+            size: None,
+            spacing: 8.0,
+            side: text_input::Side::Left,
+        });
+    }
+    let input = input
+        .secure(secure)
         .on_input(Message::QueryChanged)
         .on_submit(Message::Execute)
         .id(SEARCH_INPUT_ID)
-        .padding(12)
         .size(18)
         .width(Fill)
-        .style(theme::search_input)
-        .into()
+        .style(theme::search_input);
+
+    if query.is_empty() {
+        return input.padding(12).into();
+    }
+
+    // Overlaid on the input's right edge (which is padded to keep text clear of it).
+    // The mouse area captures the click, so the input keeps keyboard focus.
+    let clear = mouse_area(text("×").size(18).color(theme::TEXT_SECONDARY))
+        .on_press(Message::QueryChanged(String::new()))
+        .interaction(mouse::Interaction::Pointer);
+    let clear = container(clear)
+        .align_right(Fill)
+        .center_y(Fill)
+        .padding(Padding::ZERO.right(14.0));
+
+    stack![input.padding(Padding::new(12.0).right(36.0)), clear].into()
 }
@@ -1,4 +1,4 @@
-use iced::widget::{container, text_input};
+use iced::widget::{container, rule, text_input};
 use iced::{Border, Color, Shadow, Theme};
 
 /// Semi-transparent dark background color for the launcher window
@@ -9,6 +9,17 @@ pub const BACKGROUND: Color = Color {
     a: 0.92,
 };
 
+/// More translucent background used over the native blur (`window.blur`)
+const BACKGROUND_BLURRED: Color = Color {
+    r: 0.12,
+    g: 0.12,
+    b: 0.15,
+    a: 0.55,
+};
+
+/// Corner radius of the launcher container (also applied to the native blur)
+pub const CORNER_RADIUS: f32 = 12.0;
+
 /// Slightly lighter surface color for the search input
 const SURFACE: Color = Color {
     r: 0.18,
@@ -40,11 +51,36 @@ pub const TEXT_SECONDARY: Color = Color {
     a: 1.0,
 };
 
+/// Text color for errors (e.g. a config that failed to parse)
+pub const TEXT_ERROR: Color = Color {
+    r: 0.95,
+    g: 0.45,
+    b: 0.45,
+    a: 1.0,
+};
+
+/// Dim text color for source tags
+pub const TEXT_TAG: Color = Color {
+    r: 0.4,
+    g: 0.4,
+    b: 0.45,
+    a: 1.0,
+};
+
 /// Style for the main container wrapping the entire launcher
 pub fn main_container(theme: &Theme) -> container::Style {
+    main_container_with(theme, BACKGROUND)
+}
+
+/// Main container style for `window.blur`: lets the native blur show through
+pub fn main_container_blurred(theme: &Theme) -> container::Style {
+    main_container_with(theme, BACKGROUND_BLURRED)
+}
+
+fn main_container_with(theme: &Theme, background: Color) -> container::Style {
     let _ = theme;
     container::Style {
-        background: Some(BACKGROUND.into()),
+        background: Some(background.into()),
         border: Border {
             color: Color {
                 r: 0.3,
@@ -53,7 +89,7 @@ pub fn main_container(theme: &Theme) -> container::Style {
                 a: 0.5,
             },
             width: 1.0,
-            radius: 12.0.into(),
+            radius: CORNER_RADIUS.into(),
         },
         shadow: Shadow {
             color: Color::BLACK,
@@ -98,6 +134,37 @@ pub fn result_row(theme: &Theme) -> container::Style {
     }
 }
 
+/// Style for the preview pane (`window.preview`)
+pub fn preview_panel(theme: &Theme) -> container::Style {
+    let _ = theme;
+    container::Style {
+        background: Some(SURFACE.into()),
+        border: Border {
+            color: Color::TRANSPARENT,
+            width: 0.0,
+            radius: 8.0.into(),
+        },
+        text_color: Some(TEXT_PRIMARY),
+        ..container::Style::default()
+    }
+}
+
+/// Style for the line between evaluator results and provider results
+pub fn separator(theme: &Theme) -> rule::Style {
+    let _ = theme;
+    rule::Style {
+        color: Color {
+            r: 0.3,
+            g: 0.3,
+            b: 0.35,
+            a: 0.6,
+        },
+        radius: 0.0.into(),
+        fill_mode: rule::FillMode::Padded(12),
+        snap: true,
+    }
+}
+
 /// Style for the active tab in the tab bar
 pub fn tab_active(theme: &Theme) -> container::Style {
     let _ = theme;
@@ -158,3 +225,26 @@ pub fn result_row_selected(theme: &Theme) -> container::Style {
         ..container::Style::default()
     }
 }
+
+/// Style for the result row under the mouse cursor (fainter than the selection)
+pub fn result_row_hovered(theme: &Theme) -> container::Style {
+    let _ = theme;
+    container::Style {
+        background: Some(
+            Color {
+                r: ACCENT.r,
+                g: ACCENT.g,
+                b: ACCENT.b,
+                a: 0.08,
+            }
+            .into(),
+        ),
+        border: Border {
+            color: Color::TRANSPARENT,
+            width: 0.0,
+            radius: 6.0.into(),
+        },
+        text_color: Some(TEXT_PRIMARY),
+        ..container::Style::default()
+    }
+}
@@ -0,0 +1,39 @@
+use std::process;
+
+use heats_core::clipboard;
+use heats_core::platform::macos::copy_to_clipboard;
+use heats_core::source::DmenuItem;
+
+/// Longest title shown for an entry; the full text is in `data`
+const TITLE_MAX_CHARS: usize = 100;
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+
+    // --copy <text>: the action, putting a chosen entry back on the pasteboard
+    if args.first().is_some_and(|arg| arg == "--copy") {
+        let Some(text) = args.get(1) else {
+            eprintln!("Usage: heats-clipboard --copy <text>");
+            process::exit(2);
+        };
+        copy_to_clipboard(text);
+        return;
+    }
+
+    // Most recently copied first: the first line as title, the size of longer text below
+    for entry in clipboard::read() {
+        let first_line = entry.text.trim().lines().next().unwrap_or_default();
+        let mut title: String = first_line.chars().take(TITLE_MAX_CHARS).collect();
+        if title.len() < first_line.len() {
+            title.push('…');
+        }
+        let line_count = entry.text.trim().lines().count();
+        let item = DmenuItem {
+            title,
+            subtitle: (line_count > 1).then(|| format!("{line_count} lines")),
+            icon_path: None,
+            data: Some(serde_json::Value::String(entry.text)),
+        };
+        println!("{}", serde_json::to_string(&item).unwrap());
+    }
+}
@@ -32,6 +32,71 @@ fn intlit_to_float(expr: &str) -> String {
     result
 }
 
+/// Named constants substituted before evaluation.
+const CONSTANTS: &[(&str, f64)] = &[("pi", std::f64::consts::PI), ("e", std::f64::consts::E)];
+
+/// Expand hex (`0x1F`), binary (`0b1010`), and octal (`0o17`) literals to decimal,
+/// and the constants `pi` / `e` to float literals.
+/// Returns the expanded expression and whether any non-decimal literal was seen,
+/// or None if a prefixed literal is malformed.
+fn expand_literals(expr: &str) -> Option<(String, bool)> {
+    let mut result = String::with_capacity(expr.len() + 8);
+    let mut has_radix = false;
+    let bytes = expr.as_bytes();
+    let mut i = 0;
+    while i < bytes.len() {
+        let c = bytes[i];
+        if c == b'0' && i + 1 < bytes.len() && matches!(bytes[i + 1], b'x' | b'b' | b'o') {
+            let radix = match bytes[i + 1] {
+                b'x' => 16,
+                b'b' => 2,
+                _ => 8,
+            };
+            let start = i + 2;
+            let mut end = start;
+            while end < bytes.len() && bytes[end].is_ascii_alphanumeric() {
+                end += 1;
+            }
+            let value = i64::from_str_radix(&expr[start..end], radix).ok()?;
+            result.push_str(&value.to_string());
+            has_radix = true;
+            i = end;
+        } else if c.is_ascii_digit() {
+            // Decimal literal (possibly "1e3"-style): copy through untouched
+            let start = i;
+            while i < bytes.len() && (bytes[i].is_ascii_alphanumeric() || bytes[i] == b'.') {
+                i += 1;
+            }
+            result.push_str(&expr[start..i]);
+        } else if c.is_ascii_alphabetic() || c == b'_' {
+            let start = i;
+            while i < bytes.len()
+                && (bytes[i].is_ascii_alphanumeric() || bytes[i] == b'_' || bytes[i] == b':')
+            {
+                i += 1;
+            }
+            let word = &expr[start..i];
+            match CONSTANTS.iter().find(|(name, _)| *name == word) {
+                Some((_, value)) => result.push_str(&format!("{value:?}")),
+                None => result.push_str(word),
+            }
+        } else {
+            result.push(c as char);
+            i += 1;
+        }
+    }
+    Some((result, has_radix))
+}
+
+fn print_item(formatted: &str, subtitle: &str) {
+    let item = serde_json::json!({
+        "title": format!("= {formatted}"),
+        "subtitle": subtitle,
+        "data": formatted,
+    });
+    println!("{}", serde_json::to_string(&item).unwrap());
+}
+
 fn main() {
     let stdin = std::io::stdin();
     let query = match stdin.lock().lines().next() {
@@ -43,18 +108,24 @@ fn main() {
         return;
     }
 
+    let (expanded, has_radix) = match expand_literals(&query) {
+        Some(v) => v,
+        None => return,
+    };
+
     // Convert integer literals to floats to avoid integer division (1/3 → 0)
-    let float_query = intlit_to_float(&query);
+    let float_query = intlit_to_float(&expanded);
     let result = match evalexpr::eval_number(&float_query) {
         Ok(f) => f,
         Err(_) => return,
     };
 
     // Format: omit trailing ".0" for integers
-    let formatted = if result.fract() == 0.0 && result.abs() < i64::MAX as f64 {
-        format!("{}", result as i64)
-    } else {
-        format!("{result}")
+    let integer =
+        (result.fract() == 0.0 && result.abs() < i64::MAX as f64).then_some(result as i64);
+    let formatted = match integer {
+        Some(n) => n.to_string(),
+        None => format!("{result}"),
     };
 
     // Don't show result if it's the same as the input (e.g. "42" → 42)
@@ -62,10 +133,16 @@ fn main() {
         return;
     }
 
-    let item = serde_json::json!({
-        "title": format!("= {formatted}"),
-        "subtitle": "Copy to clipboard",
-        "data": formatted,
-    });
-    println!("{}", serde_json::to_string(&item).unwrap());
+    print_item(&formatted, "Copy to clipboard");
+
+    // Hex/binary input: also offer the integer result in those bases
+    if let (true, Some(n)) = (has_radix, integer) {
+        let sign = if n < 0 { "-" } else { "" };
+        let abs = n.unsigned_abs();
+        print_item(
+            &format!("{sign}0x{abs:X}"),
+            "Hexadecimal · Copy to clipboard",
+        );
+        print_item(&format!("{sign}0b{abs:b}"), "Binary · Copy to clipboard");
+    }
 }
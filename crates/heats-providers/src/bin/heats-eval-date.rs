@@ -0,0 +1,178 @@
+use std::io::BufRead;
+
+use chrono::{DateTime, Days, Local, Months, NaiveDate, TimeDelta, TimeZone};
+
+const DATETIME_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+const DATE_FORMAT: &str = "%Y-%m-%d";
+
+/// A computed answer: displayed title/subtitle and the value passed to the action
+struct Answer {
+    title: String,
+    subtitle: String,
+    data: String,
+}
+
+/// A clock or calendar offset such as "3 hours", "2 days", or "1 month"
+enum Offset {
+    /// Sub-day offsets (seconds, minutes, hours)
+    Clock(TimeDelta),
+    /// Calendar days (days, weeks), DST-aware
+    Days(u64),
+    /// Calendar months (months, years)
+    Months(u32),
+}
+
+impl Offset {
+    /// Whether applying the offset to a date keeps it a plain date
+    fn is_calendar(&self) -> bool {
+        !matches!(self, Self::Clock(_))
+    }
+}
+
+/// Local midnight at the start of the given date.
+fn local_midnight(date: NaiveDate) -> Option<DateTime<Local>> {
+    date.and_hms_opt(0, 0, 0)?
+        .and_local_timezone(Local)
+        .earliest()
+}
+
+/// Parse a local date in YYYY-MM-DD form, at midnight.
+fn parse_date(s: &str) -> Option<DateTime<Local>> {
+    local_midnight(NaiveDate::parse_from_str(s.trim(), DATE_FORMAT).ok()?)
+}
+
+/// Parse "<n> <unit>" or "<n><unit>" (e.g. "3 days", "12h", "2 months").
+fn parse_offset(s: &str) -> Option<Offset> {
+    let s = s.trim();
+    let split = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+    let n: u32 = s[..split].parse().ok()?;
+    let offset = match s[split..].trim() {
+        "s" | "sec" | "secs" | "second" | "seconds" => {
+            Offset::Clock(TimeDelta::try_seconds(n.into())?)
+        }
+        "min" | "mins" | "minute" | "minutes" => Offset::Clock(TimeDelta::try_minutes(n.into())?),
+        "h" | "hr" | "hrs" | "hour" | "hours" => Offset::Clock(TimeDelta::try_hours(n.into())?),
+        "d" | "day" | "days" => Offset::Days(n.into()),
+        "w" | "wk" | "week" | "weeks" => Offset::Days(u64::from(n) * 7),
+        "mo" | "month" | "months" => Offset::Months(n),
+        "y" | "yr" | "year" | "years" => Offset::Months(n.checked_mul(12)?),
+        _ => return None,
+    };
+    Some(offset)
+}
+
+fn apply(base: DateTime<Local>, offset: &Offset, negative: bool) -> Option<DateTime<Local>> {
+    match (offset, negative) {
+        (Offset::Clock(d), false) => base.checked_add_signed(*d),
+        (Offset::Clock(d), true) => base.checked_sub_signed(*d),
+        (Offset::Days(n), false) => base.checked_add_days(Days::new(*n)),
+        (Offset::Days(n), true) => base.checked_sub_days(Days::new(*n)),
+        (Offset::Months(m), false) => base.checked_add_months(Months::new(*m)),
+        (Offset::Months(m), true) => base.checked_sub_months(Months::new(*m)),
+    }
+}
+
+fn date_answer(dt: DateTime<Local>, date_only: bool) -> Answer {
+    let formatted = if date_only {
+        dt.format(DATE_FORMAT).to_string()
+    } else {
+        dt.format(DATETIME_FORMAT).to_string()
+    };
+    Answer {
+        title: format!("= {formatted}"),
+        subtitle: format!("{} · Copy to clipboard", dt.format("%A")),
+        data: formatted,
+    }
+}
+
+fn days_between(from: DateTime<Local>, to: DateTime<Local>, label: &str) -> Answer {
+    let days = (to.date_naive() - from.date_naive()).num_days();
+    let unit = if days.abs() == 1 { "day" } else { "days" };
+    Answer {
+        title: format!("= {days} {unit}"),
+        subtitle: format!("{label} · Copy to clipboard"),
+        data: days.to_string(),
+    }
+}
+
+/// Evaluate a date/time query relative to `now`. Returns None for unrecognized input.
+fn evaluate(query: &str, now: DateTime<Local>) -> Option<Answer> {
+    let q = query.trim().to_lowercase();
+
+    if let Some(rest) = q.strip_prefix("days until ") {
+        let target = parse_date(rest)?;
+        let label = format!("until {}", target.format("%Y-%m-%d (%A)"));
+        return Some(days_between(now, target, &label));
+    }
+    if let Some(rest) = q.strip_prefix("days since ") {
+        let origin = parse_date(rest)?;
+        let label = format!("since {}", origin.format("%Y-%m-%d (%A)"));
+        return Some(days_between(origin, now, &label));
+    }
+    if let Some(rest) = q.strip_prefix("unix") {
+        let rest = rest.trim();
+        if rest.is_empty() || rest == "now" {
+            let ts = now.timestamp();
+            return Some(Answer {
+                title: format!("= {ts}"),
+                subtitle: "Unix timestamp · Copy to clipboard".to_string(),
+                data: ts.to_string(),
+            });
+        }
+        let ts: i64 = rest.parse().ok()?;
+        let dt = Local.timestamp_opt(ts, 0).single()?;
+        return Some(date_answer(dt, false));
+    }
+
+    // "<base> [+|- <offset>]" where base is now, today, or YYYY-MM-DD
+    let (base_str, op) = match q.find(" + ").or_else(|| q.find(" - ")) {
+        Some(i) => (&q[..i], Some((&q[i + 1..i + 2] == "-", &q[i + 3..]))),
+        None => (q.as_str(), None),
+    };
+
+    let today = now.date_naive();
+    let (base, date_only) = match base_str.trim() {
+        "now" => (now, false),
+        "today" => (local_midnight(today)?, true),
+        "tomorrow" => (local_midnight(today.succ_opt()?)?, true),
+        "yesterday" => (local_midnight(today.pred_opt()?)?, true),
+        other if op.is_some() => (parse_date(other)?, true),
+        _ => return None,
+    };
+
+    match op {
+        None => Some(date_answer(base, date_only)),
+        Some((negative, offset_str)) => {
+            let offset = parse_offset(offset_str)?;
+            let result = apply(base, &offset, negative)?;
+            Some(date_answer(result, date_only && offset.is_calendar()))
+        }
+    }
+}
+
+fn main() {
+    // Query comes from the first argument (input = "arg") or stdin (input = "stdin")
+    let query = match std::env::args().nth(1) {
+        Some(arg) => arg.trim().to_string(),
+        None => match std::io::stdin().lock().lines().next() {
+            Some(Ok(line)) => line.trim().to_string(),
+            _ => return,
+        },
+    };
+
+    if query.is_empty() {
+        return;
+    }
+
+    let answer = match evaluate(&query, Local::now()) {
+        Some(a) => a,
+        None => return,
+    };
+
+    let item = serde_json::json!({
+        "title": answer.title,
+        "subtitle": answer.subtitle,
+        "data": answer.data,
+    });
+    println!("{}", serde_json::to_string(&item).unwrap());
+}
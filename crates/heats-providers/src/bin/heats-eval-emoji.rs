@@ -0,0 +1,209 @@
+use std::io::BufRead;
+
+/// Maximum number of emoji emitted per query
+const MAX_RESULTS: usize = 8;
+
+/// An emoji: the character, its name, and extra search keywords.
+struct Emoji {
+    emoji: &'static str,
+    name: &'static str,
+    keywords: &'static [&'static str],
+}
+
+#[rustfmt::skip]
+const EMOJI: &[Emoji] = &[
+    // Faces
+    Emoji { emoji: "😀", name: "grinning face", keywords: &["smile", "happy", "joy"] },
+    Emoji { emoji: "😂", name: "face with tears of joy", keywords: &["laugh", "lol", "haha", "funny"] },
+    Emoji { emoji: "🤣", name: "rolling on the floor laughing", keywords: &["rofl", "laugh", "lol"] },
+    Emoji { emoji: "😊", name: "smiling face with smiling eyes", keywords: &["smile", "blush", "happy"] },
+    Emoji { emoji: "😉", name: "winking face", keywords: &["wink", "flirt"] },
+    Emoji { emoji: "😍", name: "smiling face with heart-eyes", keywords: &["love", "crush", "heart"] },
+    Emoji { emoji: "😘", name: "face blowing a kiss", keywords: &["kiss", "love"] },
+    Emoji { emoji: "😎", name: "smiling face with sunglasses", keywords: &["cool", "sunglasses"] },
+    Emoji { emoji: "🤔", name: "thinking face", keywords: &["think", "hmm", "consider"] },
+    Emoji { emoji: "🙄", name: "face with rolling eyes", keywords: &["eyeroll", "whatever"] },
+    Emoji { emoji: "😅", name: "grinning face with sweat", keywords: &["sweat", "relief", "phew"] },
+    Emoji { emoji: "😇", name: "smiling face with halo", keywords: &["angel", "innocent"] },
+    Emoji { emoji: "🙂", name: "slightly smiling face", keywords: &["smile"] },
+    Emoji { emoji: "🙃", name: "upside-down face", keywords: &["silly", "sarcasm"] },
+    Emoji { emoji: "😐", name: "neutral face", keywords: &["meh", "blank"] },
+    Emoji { emoji: "😴", name: "sleeping face", keywords: &["sleep", "tired", "zzz"] },
+    Emoji { emoji: "😢", name: "crying face", keywords: &["cry", "sad", "tear"] },
+    Emoji { emoji: "😭", name: "loudly crying face", keywords: &["cry", "sob", "sad"] },
+    Emoji { emoji: "😡", name: "pouting face", keywords: &["angry", "mad", "rage"] },
+    Emoji { emoji: "😱", name: "face screaming in fear", keywords: &["scream", "shock", "scared"] },
+    Emoji { emoji: "😳", name: "flushed face", keywords: &["embarrassed", "blush"] },
+    Emoji { emoji: "🥳", name: "partying face", keywords: &["party", "celebrate", "birthday"] },
+    Emoji { emoji: "🥺", name: "pleading face", keywords: &["please", "puppy"] },
+    Emoji { emoji: "🤯", name: "exploding head", keywords: &["mind blown", "shock", "wow"] },
+    Emoji { emoji: "🤮", name: "face vomiting", keywords: &["sick", "vomit", "gross"] },
+    Emoji { emoji: "🤗", name: "hugging face", keywords: &["hug"] },
+    Emoji { emoji: "🤫", name: "shushing face", keywords: &["quiet", "secret", "shh"] },
+    Emoji { emoji: "💀", name: "skull", keywords: &["dead", "death"] },
+    Emoji { emoji: "👻", name: "ghost", keywords: &["halloween", "spooky"] },
+    Emoji { emoji: "🤖", name: "robot", keywords: &["bot", "ai"] },
+    Emoji { emoji: "💩", name: "pile of poo", keywords: &["poop", "shit"] },
+    // Hands & people
+    Emoji { emoji: "👍", name: "thumbs up", keywords: &["like", "yes", "ok", "approve", "+1"] },
+    Emoji { emoji: "👎", name: "thumbs down", keywords: &["dislike", "no", "-1"] },
+    Emoji { emoji: "👌", name: "ok hand", keywords: &["ok", "perfect"] },
+    Emoji { emoji: "✌️", name: "victory hand", keywords: &["peace", "victory"] },
+    Emoji { emoji: "🤞", name: "crossed fingers", keywords: &["luck", "hope"] },
+    Emoji { emoji: "👏", name: "clapping hands", keywords: &["clap", "applause", "bravo"] },
+    Emoji { emoji: "🙌", name: "raising hands", keywords: &["hooray", "celebrate", "praise"] },
+    Emoji { emoji: "🙏", name: "folded hands", keywords: &["please", "thanks", "pray"] },
+    Emoji { emoji: "👋", name: "waving hand", keywords: &["wave", "hello", "hi", "bye"] },
+    Emoji { emoji: "🤝", name: "handshake", keywords: &["deal", "agreement"] },
+    Emoji { emoji: "💪", name: "flexed biceps", keywords: &["strong", "muscle", "flex"] },
+    Emoji { emoji: "👀", name: "eyes", keywords: &["look", "see", "watch"] },
+    Emoji { emoji: "🧠", name: "brain", keywords: &["smart", "think"] },
+    Emoji { emoji: "🤷", name: "person shrugging", keywords: &["shrug", "dunno", "whatever"] },
+    Emoji { emoji: "🤦", name: "person facepalming", keywords: &["facepalm", "doh"] },
+    // Hearts & symbols
+    Emoji { emoji: "❤️", name: "red heart", keywords: &["love", "heart"] },
+    Emoji { emoji: "💔", name: "broken heart", keywords: &["heartbreak", "sad"] },
+    Emoji { emoji: "💯", name: "hundred points", keywords: &["100", "perfect", "score"] },
+    Emoji { emoji: "🔥", name: "fire", keywords: &["hot", "lit", "flame"] },
+    Emoji { emoji: "✨", name: "sparkles", keywords: &["shiny", "new", "magic"] },
+    Emoji { emoji: "⭐", name: "star", keywords: &["favorite"] },
+    Emoji { emoji: "⚡", name: "high voltage", keywords: &["lightning", "zap", "fast"] },
+    Emoji { emoji: "💥", name: "collision", keywords: &["boom", "explosion", "bang"] },
+    Emoji { emoji: "💡", name: "light bulb", keywords: &["idea", "tip"] },
+    Emoji { emoji: "✅", name: "check mark button", keywords: &["done", "yes", "ok", "check"] },
+    Emoji { emoji: "✔️", name: "check mark", keywords: &["done", "check", "tick"] },
+    Emoji { emoji: "❌", name: "cross mark", keywords: &["no", "wrong", "fail", "x"] },
+    Emoji { emoji: "⚠️", name: "warning", keywords: &["caution", "alert"] },
+    Emoji { emoji: "🚫", name: "prohibited", keywords: &["forbidden", "no", "ban"] },
+    Emoji { emoji: "❓", name: "question mark", keywords: &["question", "help"] },
+    Emoji { emoji: "❗", name: "exclamation mark", keywords: &["important", "bang"] },
+    Emoji { emoji: "➡️", name: "right arrow", keywords: &["arrow", "next"] },
+    Emoji { emoji: "⬅️", name: "left arrow", keywords: &["arrow", "back"] },
+    Emoji { emoji: "⬆️", name: "up arrow", keywords: &["arrow", "up"] },
+    Emoji { emoji: "⬇️", name: "down arrow", keywords: &["arrow", "down"] },
+    Emoji { emoji: "♻️", name: "recycling symbol", keywords: &["recycle", "refactor"] },
+    Emoji { emoji: "🔒", name: "locked", keywords: &["lock", "secure", "private"] },
+    Emoji { emoji: "🔑", name: "key", keywords: &["password", "unlock"] },
+    Emoji { emoji: "🔔", name: "bell", keywords: &["notification", "alert"] },
+    Emoji { emoji: "🔍", name: "magnifying glass", keywords: &["search", "find", "zoom"] },
+    // Work & objects
+    Emoji { emoji: "🚀", name: "rocket", keywords: &["launch", "ship", "deploy", "fast"] },
+    Emoji { emoji: "🎉", name: "party popper", keywords: &["party", "tada", "celebrate", "congrats"] },
+    Emoji { emoji: "🎁", name: "wrapped gift", keywords: &["gift", "present", "birthday"] },
+    Emoji { emoji: "🏆", name: "trophy", keywords: &["win", "award", "champion"] },
+    Emoji { emoji: "🐛", name: "bug", keywords: &["insect", "bugfix"] },
+    Emoji { emoji: "🩹", name: "adhesive bandage", keywords: &["fix", "patch"] },
+    Emoji { emoji: "🔧", name: "wrench", keywords: &["tool", "config", "fix"] },
+    Emoji { emoji: "🔨", name: "hammer", keywords: &["tool", "build"] },
+    Emoji { emoji: "⚙️", name: "gear", keywords: &["settings", "config", "cog"] },
+    Emoji { emoji: "📝", name: "memo", keywords: &["note", "write", "docs"] },
+    Emoji { emoji: "📌", name: "pushpin", keywords: &["pin", "location"] },
+    Emoji { emoji: "📎", name: "paperclip", keywords: &["attach", "clip"] },
+    Emoji { emoji: "📦", name: "package", keywords: &["box", "ship", "delivery"] },
+    Emoji { emoji: "📅", name: "calendar", keywords: &["date", "schedule"] },
+    Emoji { emoji: "📈", name: "chart increasing", keywords: &["graph", "growth", "up"] },
+    Emoji { emoji: "📉", name: "chart decreasing", keywords: &["graph", "down"] },
+    Emoji { emoji: "📧", name: "e-mail", keywords: &["email", "mail", "letter"] },
+    Emoji { emoji: "📞", name: "telephone receiver", keywords: &["phone", "call"] },
+    Emoji { emoji: "💻", name: "laptop", keywords: &["computer", "mac", "code"] },
+    Emoji { emoji: "⌨️", name: "keyboard", keywords: &["type", "input"] },
+    Emoji { emoji: "🖥️", name: "desktop computer", keywords: &["computer", "monitor", "screen"] },
+    Emoji { emoji: "📱", name: "mobile phone", keywords: &["phone", "iphone", "smartphone"] },
+    Emoji { emoji: "💾", name: "floppy disk", keywords: &["save", "disk"] },
+    Emoji { emoji: "🗑️", name: "wastebasket", keywords: &["trash", "delete", "remove"] },
+    Emoji { emoji: "⏰", name: "alarm clock", keywords: &["time", "wake", "alarm"] },
+    Emoji { emoji: "⏳", name: "hourglass not done", keywords: &["wait", "time", "loading"] },
+    Emoji { emoji: "💰", name: "money bag", keywords: &["money", "rich", "dollar"] },
+    Emoji { emoji: "💸", name: "money with wings", keywords: &["money", "spend", "pay"] },
+    Emoji { emoji: "🏠", name: "house", keywords: &["home"] },
+    Emoji { emoji: "✈️", name: "airplane", keywords: &["flight", "travel", "plane"] },
+    Emoji { emoji: "🚗", name: "automobile", keywords: &["car", "drive"] },
+    Emoji { emoji: "🚧", name: "construction", keywords: &["wip", "work in progress", "barrier"] },
+    // Nature & weather
+    Emoji { emoji: "☀️", name: "sun", keywords: &["sunny", "weather", "bright"] },
+    Emoji { emoji: "🌙", name: "crescent moon", keywords: &["moon", "night"] },
+    Emoji { emoji: "☁️", name: "cloud", keywords: &["weather", "cloudy"] },
+    Emoji { emoji: "🌧️", name: "cloud with rain", keywords: &["rain", "weather"] },
+    Emoji { emoji: "❄️", name: "snowflake", keywords: &["snow", "cold", "winter"] },
+    Emoji { emoji: "🌈", name: "rainbow", keywords: &["pride", "weather"] },
+    Emoji { emoji: "🌊", name: "water wave", keywords: &["wave", "ocean", "sea"] },
+    Emoji { emoji: "🌸", name: "cherry blossom", keywords: &["sakura", "flower", "spring"] },
+    Emoji { emoji: "🌹", name: "rose", keywords: &["flower", "love"] },
+    Emoji { emoji: "🌱", name: "seedling", keywords: &["plant", "grow", "sprout"] },
+    Emoji { emoji: "🍀", name: "four leaf clover", keywords: &["luck", "lucky", "clover"] },
+    // Animals
+    Emoji { emoji: "🐶", name: "dog face", keywords: &["dog", "puppy", "pet"] },
+    Emoji { emoji: "🐱", name: "cat face", keywords: &["cat", "kitten", "pet"] },
+    Emoji { emoji: "🦊", name: "fox", keywords: &["animal"] },
+    Emoji { emoji: "🐻", name: "bear", keywords: &["animal"] },
+    Emoji { emoji: "🐼", name: "panda", keywords: &["animal"] },
+    Emoji { emoji: "🐧", name: "penguin", keywords: &["linux", "animal"] },
+    Emoji { emoji: "🦀", name: "crab", keywords: &["rust", "ferris"] },
+    Emoji { emoji: "🐍", name: "snake", keywords: &["python"] },
+    Emoji { emoji: "🦄", name: "unicorn", keywords: &["magic", "fantasy"] },
+    Emoji { emoji: "🐢", name: "turtle", keywords: &["slow", "tortoise"] },
+    // Food & drink
+    Emoji { emoji: "☕", name: "hot beverage", keywords: &["coffee", "tea", "cafe"] },
+    Emoji { emoji: "🍵", name: "teacup without handle", keywords: &["tea", "matcha", "green tea"] },
+    Emoji { emoji: "🍺", name: "beer mug", keywords: &["beer", "drink", "cheers"] },
+    Emoji { emoji: "🍷", name: "wine glass", keywords: &["wine", "drink"] },
+    Emoji { emoji: "🍕", name: "pizza", keywords: &["food"] },
+    Emoji { emoji: "🍔", name: "hamburger", keywords: &["burger", "food"] },
+    Emoji { emoji: "🍣", name: "sushi", keywords: &["food", "japanese"] },
+    Emoji { emoji: "🍜", name: "steaming bowl", keywords: &["ramen", "noodles", "food"] },
+    Emoji { emoji: "🍰", name: "shortcake", keywords: &["cake", "dessert"] },
+    Emoji { emoji: "🎂", name: "birthday cake", keywords: &["birthday", "cake"] },
+    Emoji { emoji: "🍎", name: "red apple", keywords: &["apple", "fruit"] },
+    Emoji { emoji: "🍌", name: "banana", keywords: &["fruit"] },
+    Emoji { emoji: "🍓", name: "strawberry", keywords: &["fruit", "berry"] },
+];
+
+/// Match quality of an emoji against a query (lower is better).
+fn rank(emoji: &Emoji, query: &str) -> Option<u8> {
+    let words = || {
+        emoji
+            .name
+            .split([' ', '-'])
+            .chain(emoji.keywords.iter().copied())
+    };
+    if emoji.name == query || emoji.keywords.contains(&query) {
+        Some(0)
+    } else if words().any(|w| w == query) {
+        Some(1)
+    } else if words().any(|w| w.starts_with(query)) {
+        Some(2)
+    } else if emoji.name.contains(query) {
+        Some(3)
+    } else {
+        None
+    }
+}
+
+fn main() {
+    let stdin = std::io::stdin();
+    let query = match stdin.lock().lines().next() {
+        Some(Ok(line)) => line.trim().to_lowercase(),
+        _ => return,
+    };
+
+    // Single characters match nearly everything; wait for a real word
+    if query.chars().count() < 2 {
+        return;
+    }
+
+    let mut matches: Vec<(u8, &Emoji)> = EMOJI
+        .iter()
+        .filter_map(|e| rank(e, &query).map(|r| (r, e)))
+        .collect();
+    // Stable sort keeps table order within the same rank
+    matches.sort_by_key(|(r, _)| *r);
+
+    for (_, e) in matches.into_iter().take(MAX_RESULTS) {
+        let item = serde_json::json!({
+            "title": e.emoji,
+            "subtitle": format!("{} · Copy to clipboard", e.name),
+            "data": e.emoji,
+        });
+        println!("{}", serde_json::to_string(&item).unwrap());
+    }
+}
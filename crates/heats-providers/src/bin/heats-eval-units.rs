@@ -0,0 +1,164 @@
+use std::io::BufRead;
+
+/// Physical quantity a unit measures. Only units of the same kind convert.
+#[derive(Clone, Copy, PartialEq)]
+enum Kind {
+    Length,
+    Mass,
+    Data,
+    Time,
+    Volume,
+    Temperature,
+}
+
+/// A unit: accepted spellings, display symbol, kind, and factor to the kind's base unit.
+/// Temperature units ignore `factor` and convert via `to_kelvin` / `from_kelvin`.
+struct Unit {
+    names: &'static [&'static str],
+    symbol: &'static str,
+    kind: Kind,
+    factor: f64,
+}
+
+#[rustfmt::skip]
+const UNITS: &[Unit] = &[
+    // Length (base: meter)
+    Unit { names: &["mm", "millimeter", "millimeters"], symbol: "mm", kind: Kind::Length, factor: 0.001 },
+    Unit { names: &["cm", "centimeter", "centimeters"], symbol: "cm", kind: Kind::Length, factor: 0.01 },
+    Unit { names: &["m", "meter", "meters", "metre", "metres"], symbol: "m", kind: Kind::Length, factor: 1.0 },
+    Unit { names: &["km", "kilometer", "kilometers", "kilometre", "kilometres"], symbol: "km", kind: Kind::Length, factor: 1000.0 },
+    Unit { names: &["in", "inch", "inches"], symbol: "in", kind: Kind::Length, factor: 0.0254 },
+    Unit { names: &["ft", "foot", "feet"], symbol: "ft", kind: Kind::Length, factor: 0.3048 },
+    Unit { names: &["yd", "yard", "yards"], symbol: "yd", kind: Kind::Length, factor: 0.9144 },
+    Unit { names: &["mi", "mile", "miles"], symbol: "mi", kind: Kind::Length, factor: 1609.344 },
+    Unit { names: &["nmi", "nauticalmile", "nauticalmiles"], symbol: "nmi", kind: Kind::Length, factor: 1852.0 },
+    // Mass (base: kilogram)
+    Unit { names: &["mg", "milligram", "milligrams"], symbol: "mg", kind: Kind::Mass, factor: 0.000001 },
+    Unit { names: &["g", "gram", "grams"], symbol: "g", kind: Kind::Mass, factor: 0.001 },
+    Unit { names: &["kg", "kilogram", "kilograms", "kilo", "kilos"], symbol: "kg", kind: Kind::Mass, factor: 1.0 },
+    Unit { names: &["t", "tonne", "tonnes"], symbol: "t", kind: Kind::Mass, factor: 1000.0 },
+    Unit { names: &["oz", "ounce", "ounces"], symbol: "oz", kind: Kind::Mass, factor: 0.028349523125 },
+    Unit { names: &["lb", "lbs", "pound", "pounds"], symbol: "lb", kind: Kind::Mass, factor: 0.45359237 },
+    Unit { names: &["st", "stone", "stones"], symbol: "st", kind: Kind::Mass, factor: 6.35029318 },
+    // Data (base: byte)
+    Unit { names: &["bit", "bits"], symbol: "bit", kind: Kind::Data, factor: 0.125 },
+    Unit { names: &["b", "byte", "bytes"], symbol: "B", kind: Kind::Data, factor: 1.0 },
+    Unit { names: &["kb", "kilobyte", "kilobytes"], symbol: "KB", kind: Kind::Data, factor: 1e3 },
+    Unit { names: &["mb", "megabyte", "megabytes"], symbol: "MB", kind: Kind::Data, factor: 1e6 },
+    Unit { names: &["gb", "gigabyte", "gigabytes"], symbol: "GB", kind: Kind::Data, factor: 1e9 },
+    Unit { names: &["tb", "terabyte", "terabytes"], symbol: "TB", kind: Kind::Data, factor: 1e12 },
+    Unit { names: &["kib", "kibibyte", "kibibytes"], symbol: "KiB", kind: Kind::Data, factor: 1024.0 },
+    Unit { names: &["mib", "mebibyte", "mebibytes"], symbol: "MiB", kind: Kind::Data, factor: 1048576.0 },
+    Unit { names: &["gib", "gibibyte", "gibibytes"], symbol: "GiB", kind: Kind::Data, factor: 1073741824.0 },
+    Unit { names: &["tib", "tebibyte", "tebibytes"], symbol: "TiB", kind: Kind::Data, factor: 1099511627776.0 },
+    // Time (base: second)
+    Unit { names: &["ms", "millisecond", "milliseconds"], symbol: "ms", kind: Kind::Time, factor: 0.001 },
+    Unit { names: &["s", "sec", "secs", "second", "seconds"], symbol: "s", kind: Kind::Time, factor: 1.0 },
+    Unit { names: &["min", "mins", "minute", "minutes"], symbol: "min", kind: Kind::Time, factor: 60.0 },
+    Unit { names: &["h", "hr", "hrs", "hour", "hours"], symbol: "h", kind: Kind::Time, factor: 3600.0 },
+    Unit { names: &["d", "day", "days"], symbol: "d", kind: Kind::Time, factor: 86400.0 },
+    Unit { names: &["wk", "week", "weeks"], symbol: "wk", kind: Kind::Time, factor: 604800.0 },
+    // Volume (base: liter)
+    Unit { names: &["ml", "milliliter", "milliliters", "millilitre", "millilitres"], symbol: "ml", kind: Kind::Volume, factor: 0.001 },
+    Unit { names: &["l", "liter", "liters", "litre", "litres"], symbol: "l", kind: Kind::Volume, factor: 1.0 },
+    Unit { names: &["floz"], symbol: "fl oz", kind: Kind::Volume, factor: 0.0295735295625 },
+    Unit { names: &["cup", "cups"], symbol: "cup", kind: Kind::Volume, factor: 0.2365882365 },
+    Unit { names: &["pt", "pint", "pints"], symbol: "pt", kind: Kind::Volume, factor: 0.473176473 },
+    Unit { names: &["qt", "quart", "quarts"], symbol: "qt", kind: Kind::Volume, factor: 0.946352946 },
+    Unit { names: &["gal", "gallon", "gallons"], symbol: "gal", kind: Kind::Volume, factor: 3.785411784 },
+    // Temperature (converted via kelvin)
+    Unit { names: &["c", "°c", "celsius"], symbol: "°C", kind: Kind::Temperature, factor: 1.0 },
+    Unit { names: &["f", "°f", "fahrenheit"], symbol: "°F", kind: Kind::Temperature, factor: 1.0 },
+    Unit { names: &["k", "kelvin"], symbol: "K", kind: Kind::Temperature, factor: 1.0 },
+];
+
+fn find_unit(name: &str) -> Option<&'static Unit> {
+    let name = name.to_lowercase();
+    UNITS.iter().find(|u| u.names.contains(&name.as_str()))
+}
+
+fn to_kelvin(value: f64, unit: &Unit) -> f64 {
+    match unit.symbol {
+        "°C" => value + 273.15,
+        "°F" => (value - 32.0) * 5.0 / 9.0 + 273.15,
+        _ => value,
+    }
+}
+
+fn from_kelvin(value: f64, unit: &Unit) -> f64 {
+    match unit.symbol {
+        "°C" => value - 273.15,
+        "°F" => (value - 273.15) * 9.0 / 5.0 + 32.0,
+        _ => value,
+    }
+}
+
+/// Parse "<number> <unit> (to|in|->) <unit>" (the space after the number is optional).
+fn parse(query: &str) -> Option<(f64, &'static Unit, &'static Unit)> {
+    let lower = query.to_lowercase();
+    let (lhs, rhs) = [" to ", " in ", " -> ", "->"]
+        .iter()
+        .find_map(|sep| lower.split_once(sep))?;
+
+    let lhs = lhs.trim();
+    let split = lhs
+        .find(|c: char| !(c.is_ascii_digit() || c == '.' || c == '-' || c == ','))
+        .unwrap_or(lhs.len());
+    let value: f64 = lhs[..split].replace(',', "").parse().ok()?;
+    let from = find_unit(&lhs[split..].trim().replace(' ', ""))?;
+    let to = find_unit(&rhs.trim().replace(' ', ""))?;
+
+    if from.kind != to.kind {
+        return None;
+    }
+    Some((value, from, to))
+}
+
+fn convert(value: f64, from: &Unit, to: &Unit) -> f64 {
+    if from.kind == Kind::Temperature {
+        from_kelvin(to_kelvin(value, from), to)
+    } else {
+        value * from.factor / to.factor
+    }
+}
+
+/// Format with up to 6 decimal places, trimming trailing zeros.
+fn format_number(value: f64) -> String {
+    let s = format!("{value:.6}");
+    let s = s.trim_end_matches('0').trim_end_matches('.');
+    if s == "-0" {
+        "0".to_string()
+    } else {
+        s.to_string()
+    }
+}
+
+fn main() {
+    let stdin = std::io::stdin();
+    let query = match stdin.lock().lines().next() {
+        Some(Ok(line)) => line.trim().to_string(),
+        _ => return,
+    };
+
+    if query.is_empty() {
+        return;
+    }
+
+    let (value, from, to) = match parse(&query) {
+        Some(parsed) => parsed,
+        None => return,
+    };
+
+    let result = convert(value, from, to);
+    if !result.is_finite() {
+        return;
+    }
+    let formatted = format_number(result);
+
+    let item = serde_json::json!({
+        "title": format!("= {formatted} {}", to.symbol),
+        "subtitle": format!("{} {} → {} · Copy to clipboard", format_number(value), from.symbol, to.symbol),
+        "data": formatted,
+    });
+    println!("{}", serde_json::to_string(&item).unwrap());
+}
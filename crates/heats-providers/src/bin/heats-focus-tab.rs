@@ -0,0 +1,19 @@
+use std::process;
+
+use heats_core::source::tabs::focus_tab;
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let [browser, window, tab] = args.as_slice() else {
+        eprintln!("Usage: heats-focus-tab <browser> <window> <tab>");
+        process::exit(2);
+    };
+    let (Ok(window), Ok(tab)) = (window.parse::<usize>(), tab.parse::<usize>()) else {
+        eprintln!("heats-focus-tab: window and tab must be numbers");
+        process::exit(2);
+    };
+
+    if !focus_tab(browser, window, tab) {
+        process::exit(1);
+    }
+}
@@ -2,13 +2,23 @@ use heats_core::source::applications::scan_apps;
 use heats_core::source::DmenuItem;
 
 fn main() {
-    let apps = scan_apps();
+    // --exclude <pattern> (repeatable): skip apps whose name or path matches, e.g.
+    // "*Uninstaller*" or "/System/Applications/Utilities/*"
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let exclude: Vec<String> = args
+        .windows(2)
+        .filter(|pair| pair[0] == "--exclude")
+        .map(|pair| pair[1].clone())
+        .collect();
+
+    let apps = scan_apps(&exclude);
     for app in apps {
+        // Subtitle shows the version (falling back to the path); the path stays in data.path
         let item = DmenuItem {
             title: app.name,
-            subtitle: Some(app.path.clone()),
+            subtitle: Some(app.version.clone().unwrap_or_else(|| app.path.clone())),
             icon_path: Some(app.path.clone()),
-            data: Some(serde_json::json!({ "path": app.path })),
+            data: Some(serde_json::json!({ "path": app.path, "version": app.version })),
         };
         println!("{}", serde_json::to_string(&item).unwrap());
     }
@@ -0,0 +1,22 @@
+use std::process;
+
+use heats_core::history;
+use heats_core::source::DmenuItem;
+
+fn main() {
+    let Some(evaluator) = std::env::args().nth(1) else {
+        eprintln!("Usage: heats-list-history <evaluator>");
+        process::exit(2);
+    };
+
+    // Most recently chosen first: the result as title, the query it came from below
+    for entry in history::read(&evaluator) {
+        let item = DmenuItem {
+            title: entry.title,
+            subtitle: Some(entry.query),
+            icon_path: None,
+            data: Some(serde_json::Value::String(entry.value)),
+        };
+        println!("{}", serde_json::to_string(&item).unwrap());
+    }
+}
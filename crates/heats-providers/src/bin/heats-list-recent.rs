@@ -0,0 +1,22 @@
+use std::path::Path;
+
+use heats_core::recent;
+use heats_core::source::DmenuItem;
+
+fn main() {
+    // Most recently launched first; apps that have since been removed are skipped
+    for app in recent::read() {
+        let path = Path::new(&app.path);
+        if !path.exists() {
+            continue;
+        }
+        let name = path.file_stem().unwrap_or_default().to_string_lossy();
+        let item = DmenuItem {
+            title: name.to_string(),
+            subtitle: Some(app.path.clone()),
+            icon_path: Some(app.path.clone()),
+            data: Some(serde_json::json!({ "path": app.path })),
+        };
+        println!("{}", serde_json::to_string(&item).unwrap());
+    }
+}
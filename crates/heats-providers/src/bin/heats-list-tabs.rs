@@ -0,0 +1,30 @@
+use std::path::Path;
+
+use heats_core::source::tabs::scan_tabs;
+use heats_core::source::DmenuItem;
+
+fn main() {
+    // Safari and Chrome tabs of running browsers; none at all is a valid (empty) list
+    for entry in scan_tabs() {
+        let browser = entry.browser;
+        let title = if entry.title.is_empty() {
+            entry.url.clone()
+        } else {
+            entry.title
+        };
+        let item = DmenuItem {
+            title,
+            subtitle: Some(format!("{} · {}", browser.name, entry.url)),
+            icon_path: Path::new(browser.bundle_path)
+                .exists()
+                .then(|| browser.bundle_path.to_string()),
+            data: Some(serde_json::json!({
+                "browser": browser.name,
+                "window": entry.window,
+                "tab": entry.tab,
+                "url": entry.url,
+            })),
+        };
+        println!("{}", serde_json::to_string(&item).unwrap());
+    }
+}
@@ -1,21 +1,58 @@
+use std::collections::HashSet;
+
 use heats_core::platform::macos::ensure_screen_capture_access;
-use heats_core::source::windows::scan_windows_raw;
+use heats_core::source::applications::bundle_identifier;
+use heats_core::source::windows::{scan_windows_raw, WindowEntry};
 use heats_core::source::DmenuItem;
 
 fn main() {
     ensure_screen_capture_access();
 
-    let entries = scan_windows_raw();
+    // --app <name or bundle id> (repeatable): only list windows of these apps
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let apps: Vec<&str> = args
+        .windows(2)
+        .filter(|pair| pair[0] == "--app")
+        .map(|pair| pair[1].as_str())
+        .collect();
+
+    let mut entries = scan_windows_raw();
+    if !apps.is_empty() {
+        entries.retain(|entry| apps.iter().any(|app| is_app(entry, app)));
+    }
+    // "Space N" only helps when the listed windows are on different desktops
+    let multiple_spaces = entries
+        .iter()
+        .map(|entry| entry.space)
+        .collect::<HashSet<_>>()
+        .len()
+        > 1;
     for entry in entries {
+        let subtitle = match entry.space {
+            Some(space) if multiple_spaces => format!("{} · Space {space}", entry.title),
+            _ => entry.title,
+        };
         let item = DmenuItem {
             title: entry.owner,
-            subtitle: Some(entry.title),
+            subtitle: Some(subtitle),
             icon_path: entry.bundle_path,
             data: Some(serde_json::json!({
                 "pid": entry.pid,
                 "wid": entry.wid,
+                "space": entry.space,
             })),
         };
         println!("{}", serde_json::to_string(&item).unwrap());
     }
 }
+
+/// Whether the window belongs to `app`, given as the owner name (case-insensitive)
+/// or the bundle identifier.
+fn is_app(entry: &WindowEntry, app: &str) -> bool {
+    entry.owner.eq_ignore_ascii_case(app)
+        || entry
+            .bundle_path
+            .as_deref()
+            .and_then(|path| bundle_identifier(std::path::Path::new(path)))
+            .is_some_and(|id| id.eq_ignore_ascii_case(app))
+}